@@ -0,0 +1,356 @@
+use std::io;
+
+use serde::ser::{Impossible, Serialize, SerializeMap, SerializeStruct};
+
+use crate::error::{Error, ErrorCode, Kind, Result};
+use crate::maybe_undefined;
+use crate::{Compount, Formatter, Serializer, State};
+
+/// Writes a struct or map as a field argument list, `(a:1,b:"x")`, instead
+/// of as an object.
+///
+/// Nothing is written for a struct or map without entries, so the output can
+/// follow a field name directly. Values are written by the wrapped
+/// `Serializer` and follow its formatter and policies. Any other root value
+/// is an error.
+pub struct ArgumentsSerializer<'a, W: 'a, F: 'a> {
+    ser: &'a mut Serializer<W, F>,
+}
+
+impl<'a, W, F> ArgumentsSerializer<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    pub fn new(ser: &'a mut Serializer<W, F>) -> Self {
+        ArgumentsSerializer { ser }
+    }
+
+    fn expected_object(kind: Kind) -> Error {
+        Error::from_code(ErrorCode::ArgumentsExpectedObject { kind })
+    }
+}
+
+impl<'a, W, F> serde::Serializer for ArgumentsSerializer<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Arguments<'a, W, F>;
+    type SerializeStruct = Arguments<'a, W, F>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<()> {
+        Err(Self::expected_object(Kind::Bool))
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<()> {
+        Err(Self::expected_object(Kind::I128))
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<()> {
+        Err(Self::expected_object(Kind::Integer))
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<()> {
+        Err(Self::expected_object(Kind::U128))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<()> {
+        Err(Self::expected_object(Kind::Float))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<()> {
+        Err(Self::expected_object(Kind::Float))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<()> {
+        Err(Self::expected_object(Kind::Char))
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<()> {
+        Err(Self::expected_object(Kind::String))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<()> {
+        Err(Self::expected_object(Kind::Bytes))
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Err(Self::expected_object(Kind::None))
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Err(Self::expected_object(Kind::Some))
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Err(Self::expected_object(Kind::Unit))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Err(Self::expected_object(Kind::UnitStruct))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        Err(Self::expected_object(Kind::UnitVariant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Err(Self::expected_object(Kind::NewtypeVariant))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Self::expected_object(Kind::Seq))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(Self::expected_object(Kind::Tuple))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(Self::expected_object(Kind::TupleStruct))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(Self::expected_object(Kind::TupleVariant))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(Arguments {
+            ser: self.ser,
+            state: State::First,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Ok(Arguments {
+            ser: self.ser,
+            state: State::First,
+        })
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(Self::expected_object(Kind::StructVariant))
+    }
+}
+
+/// The entries of an argument list. The opening parenthesis is only written
+/// once the first entry is, so an empty list leaves no trace.
+pub struct Arguments<'a, W: 'a, F: 'a> {
+    ser: &'a mut Serializer<W, F>,
+    state: State,
+}
+
+impl<'a, W, F> Arguments<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    /// Opens the list if needed and returns an object compound positioned at
+    /// the next entry.
+    fn entry(&mut self) -> Result<Compount<'_, W, F>> {
+        let state = match self.state {
+            State::First => {
+                self.ser
+                    .formatter
+                    .begin_arguments(&mut self.ser.writer)
+                    .map_err(Error::io)?;
+                State::First
+            }
+            _ => State::Rest,
+        };
+        self.state = State::Rest;
+
+        Ok(Compount::Map {
+            ser: &mut *self.ser,
+            state,
+        })
+    }
+}
+
+impl<'a, W, F> SerializeMap for Arguments<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        self.entry()?.serialize_key(key)
+    }
+
+    fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result<()>
+    where
+        K: Serialize,
+        K: ?Sized,
+        V: Serialize,
+        V: ?Sized,
+    {
+        if maybe_undefined::is_undefined(value) {
+            return Ok(());
+        }
+
+        self.serialize_key(key)?;
+        self.serialize_value(value)
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Compount::Map {
+            ser: &mut *self.ser,
+            state: State::Rest,
+        }
+        .serialize_value(value)
+    }
+
+    fn end(self) -> Result<()> {
+        match self.state {
+            State::Rest => self
+                .ser
+                .formatter
+                .end_arguments(&mut self.ser.writer)
+                .map_err(Error::io),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<'a, W, F> SerializeStruct for Arguments<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        if maybe_undefined::is_undefined(value) {
+            return Ok(());
+        }
+
+        self.entry()?.serialize_defined_field(key, value)
+    }
+
+    fn end(self) -> Result<()> {
+        SerializeMap::end(self)
+    }
+}
+
+pub fn to_arguments_writer<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::new(writer);
+    value.serialize(ArgumentsSerializer::new(&mut ser))
+}
+
+pub fn to_arguments_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    to_arguments_writer(&mut writer, value)?;
+    Ok(writer)
+}
+
+pub fn to_arguments_string<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let vec = to_arguments_vec(value)?;
+    let string = unsafe { String::from_utf8_unchecked(vec) };
+
+    Ok(string)
+}
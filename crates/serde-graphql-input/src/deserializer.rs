@@ -0,0 +1,1018 @@
+use std::io;
+
+use serde::de::{self, DeserializeOwned, IntoDeserializer, Visitor};
+use serde::Deserialize;
+
+use crate::error::{Error, ErrorCode, Result};
+use crate::one_of;
+use crate::raw;
+use crate::value;
+use crate::variable;
+
+pub struct Deserializer<'de> {
+    slice: &'de [u8],
+    index: usize,
+    scratch: Vec<u8>,
+    remaining_depth: u8,
+}
+
+enum Reference<'b, 'c> {
+    Borrowed(&'b str),
+    Copied(&'c str),
+}
+
+enum Number {
+    PosInt(u64),
+    NegInt(i64),
+    Float(f64),
+}
+
+/// Runs `$body` one list or object level deeper, failing once nesting
+/// reaches the limit instead of overflowing the stack.
+macro_rules! check_recursion {
+    ($this:ident $($body:tt)*) => {
+        $this.remaining_depth -= 1;
+        if $this.remaining_depth == 0 {
+            return Err($this.peek_error(ErrorCode::RecursionLimitExceeded));
+        }
+
+        $this $($body)*
+
+        $this.remaining_depth += 1;
+    };
+}
+
+impl<'de> Deserializer<'de> {
+    pub fn from_slice(slice: &'de [u8]) -> Self {
+        Deserializer {
+            slice,
+            index: 0,
+            scratch: Vec::new(),
+            remaining_depth: 128,
+        }
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &'de str) -> Self {
+        Deserializer::from_slice(s.as_bytes())
+    }
+
+    /// Checks that only ignored tokens remain in the input. Should be called
+    /// after deserializing a value to reject trailing characters.
+    pub fn end(&mut self) -> Result<()> {
+        match self.parse_whitespace() {
+            Some(_) => Err(self.peek_error(ErrorCode::TrailingCharacters)),
+            None => Ok(()),
+        }
+    }
+
+    /// Parses a single value that is embedded verbatim in other input, so
+    /// only whitespace and line terminators may surround it. A comma or a
+    /// comment next to the value would change the meaning of the text around
+    /// it, as a comment runs to the end of the line.
+    pub(crate) fn parse_embeddable_value(&mut self) -> Result<()> {
+        self.skip_spaces();
+        if let Some(b'#' | b',' | 0xEF) = self.peek() {
+            return Err(self.peek_error(ErrorCode::ExpectedSomeValue));
+        }
+
+        de::IgnoredAny::deserialize(&mut *self)?;
+
+        self.skip_spaces();
+        match self.peek() {
+            Some(_) => Err(self.peek_error(ErrorCode::TrailingCharacters)),
+            None => Ok(()),
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.eat_char();
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.slice.get(self.index).copied()
+    }
+
+    fn eat_char(&mut self) {
+        self.index += 1;
+    }
+
+    fn next_char(&mut self) -> Option<u8> {
+        let ch = self.peek();
+        if ch.is_some() {
+            self.index += 1;
+        }
+        ch
+    }
+
+    fn position_of(&self, index: usize) -> (usize, usize) {
+        let mut line = 1;
+        let mut column = 0;
+        for &ch in &self.slice[..index.min(self.slice.len())] {
+            if ch == b'\n' {
+                line += 1;
+                column = 0;
+            } else {
+                column += 1;
+            }
+        }
+        (line, column)
+    }
+
+    fn error(&self, code: ErrorCode) -> Error {
+        let (line, column) = self.position_of(self.index);
+        Error::syntax(code, line, column)
+    }
+
+    fn peek_error(&self, code: ErrorCode) -> Error {
+        let (line, column) = self.position_of(self.index + 1);
+        Error::syntax(code, line, column)
+    }
+
+    fn fix_position(&self, err: Error) -> Error {
+        err.fix_position(|code| self.error(code))
+    }
+
+    /// Skips GraphQL ignored tokens (whitespace, line terminators, commas,
+    /// unicode BOM and comments) and returns the next significant byte.
+    fn parse_whitespace(&mut self) -> Option<u8> {
+        loop {
+            match self.peek() {
+                Some(b' ' | b'\t' | b'\n' | b'\r' | b',') => self.eat_char(),
+                Some(0xEF) if self.slice[self.index..].starts_with(b"\xEF\xBB\xBF") => {
+                    self.index += 3;
+                }
+                Some(b'#') => {
+                    while let Some(ch) = self.peek() {
+                        if ch == b'\n' || ch == b'\r' {
+                            break;
+                        }
+                        self.eat_char();
+                    }
+                }
+                other => return other,
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<&'de str> {
+        let start = self.index;
+        match self.peek() {
+            Some(ch) if is_name_start(ch) => self.eat_char(),
+            Some(_) => return Err(self.peek_error(ErrorCode::ExpectedName)),
+            None => return Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        }
+        while let Some(ch) = self.peek() {
+            if !is_name_continue(ch) {
+                break;
+            }
+            self.eat_char();
+        }
+
+        let slice: &'de [u8] = self.slice;
+        // Names are restricted to ASCII so this can never fail.
+        Ok(std::str::from_utf8(&slice[start..self.index]).expect("name is ascii"))
+    }
+
+    fn parse_str<'s>(&'s mut self) -> Result<Reference<'de, 's>> {
+        if self.slice[self.index..].starts_with(br#"""""#) {
+            return self.parse_block_str();
+        }
+
+        // Opening quote.
+        self.eat_char();
+        self.scratch.clear();
+
+        let mut start = self.index;
+        loop {
+            match self.peek() {
+                None => return Err(self.error(ErrorCode::EofWhileParsing)),
+                Some(b'"') => {
+                    if self.scratch.is_empty() {
+                        let slice: &'de [u8] = self.slice;
+                        let borrowed = &slice[start..self.index];
+                        self.eat_char();
+                        return as_str(self, borrowed).map(Reference::Borrowed);
+                    }
+                    let end = self.index;
+                    self.scratch.extend_from_slice(&self.slice[start..end]);
+                    self.eat_char();
+                    // Only ever pushes complete utf-8 sequences.
+                    let copied = std::str::from_utf8(&self.scratch)
+                        .map_err(|_| self.error(ErrorCode::InvalidUnicodeCodePoint))?;
+                    return Ok(Reference::Copied(copied));
+                }
+                Some(b'\\') => {
+                    let end = self.index;
+                    self.scratch.extend_from_slice(&self.slice[start..end]);
+                    self.eat_char();
+                    self.parse_escape()?;
+                    start = self.index;
+                }
+                Some(ch) if ch < 0x20 && ch != b'\t' => {
+                    return Err(self.error(ErrorCode::ControlCharacterWhileParsingString));
+                }
+                Some(_) => self.eat_char(),
+            }
+        }
+    }
+
+    fn parse_block_str<'s>(&'s mut self) -> Result<Reference<'de, 's>> {
+        // Opening quotes.
+        self.index += 3;
+        self.scratch.clear();
+
+        loop {
+            let rest = &self.slice[self.index..];
+            match self.peek() {
+                None => return Err(self.error(ErrorCode::EofWhileParsing)),
+                Some(b'"') if rest.starts_with(br#"""""#) => {
+                    self.index += 3;
+                    break;
+                }
+                Some(b'\\') if rest.starts_with(br#"\""""#) => {
+                    self.scratch.extend_from_slice(br#"""""#);
+                    self.index += 4;
+                }
+                Some(ch) if ch < 0x20 && !matches!(ch, b'\t' | b'\n' | b'\r') => {
+                    return Err(self.error(ErrorCode::ControlCharacterWhileParsingString));
+                }
+                Some(ch) => {
+                    self.scratch.push(ch);
+                    self.eat_char();
+                }
+            }
+        }
+
+        let raw = as_str(self, &self.scratch)?;
+        self.scratch = block_string_value(raw).into_bytes();
+        // Built from a valid str, splitting only at ascii characters.
+        let copied = std::str::from_utf8(&self.scratch).expect("block string is utf-8");
+        Ok(Reference::Copied(copied))
+    }
+
+    fn parse_escape(&mut self) -> Result<()> {
+        let ch = match self.next_char() {
+            Some(ch) => ch,
+            None => return Err(self.error(ErrorCode::EofWhileParsing)),
+        };
+
+        match ch {
+            b'"' => self.scratch.push(b'"'),
+            b'\\' => self.scratch.push(b'\\'),
+            b'/' => self.scratch.push(b'/'),
+            b'b' => self.scratch.push(b'\x08'),
+            b'f' => self.scratch.push(b'\x0c'),
+            b'n' => self.scratch.push(b'\n'),
+            b'r' => self.scratch.push(b'\r'),
+            b't' => self.scratch.push(b'\t'),
+            b'u' if self.peek() == Some(b'{') => {
+                self.eat_char();
+                let c = self.decode_code_point_escape()?;
+                self.scratch
+                    .extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes());
+            }
+            b'u' => {
+                let c = match self.decode_hex_escape()? {
+                    n @ 0xD800..=0xDBFF => {
+                        if self.next_char() != Some(b'\\') || self.next_char() != Some(b'u') {
+                            return Err(self.error(ErrorCode::InvalidUnicodeCodePoint));
+                        }
+                        let n2 = self.decode_hex_escape()?;
+                        if !(0xDC00..=0xDFFF).contains(&n2) {
+                            return Err(self.error(ErrorCode::InvalidUnicodeCodePoint));
+                        }
+                        let n = (((n - 0xD800) << 10) | (n2 - 0xDC00)) + 0x1_0000;
+                        char::from_u32(n)
+                    }
+                    n => char::from_u32(n),
+                };
+                let c = c.ok_or_else(|| self.error(ErrorCode::InvalidUnicodeCodePoint))?;
+                self.scratch
+                    .extend_from_slice(c.encode_utf8(&mut [0_u8; 4]).as_bytes());
+            }
+            _ => return Err(self.error(ErrorCode::InvalidEscape)),
+        }
+
+        Ok(())
+    }
+
+    /// Decodes the `X}` of a `\u{X}` escape.
+    fn decode_code_point_escape(&mut self) -> Result<char> {
+        let mut n: u32 = 0;
+        let mut digits = 0;
+        loop {
+            let digit = match self.next_char() {
+                Some(b'}') if digits > 0 => break,
+                Some(ch) => (ch as char).to_digit(16),
+                None => return Err(self.error(ErrorCode::EofWhileParsing)),
+            };
+            match digit {
+                Some(digit) if n <= 0x10_FFFF => n = (n << 4) | digit,
+                Some(_) => return Err(self.error(ErrorCode::InvalidUnicodeCodePoint)),
+                None => return Err(self.error(ErrorCode::InvalidEscape)),
+            }
+            digits += 1;
+        }
+        char::from_u32(n).ok_or_else(|| self.error(ErrorCode::InvalidUnicodeCodePoint))
+    }
+
+    fn decode_hex_escape(&mut self) -> Result<u32> {
+        let mut n = 0;
+        for _ in 0..4 {
+            let digit = match self.next_char() {
+                Some(ch) => (ch as char).to_digit(16),
+                None => return Err(self.error(ErrorCode::EofWhileParsing)),
+            };
+            match digit {
+                Some(digit) => n = (n << 4) | digit,
+                None => return Err(self.error(ErrorCode::InvalidEscape)),
+            }
+        }
+        Ok(n)
+    }
+
+    fn parse_number(&mut self) -> Result<Number> {
+        let start = self.index;
+        if self.peek() == Some(b'-') {
+            self.eat_char();
+        }
+
+        let mut is_float = false;
+        match self.next_char() {
+            Some(b'0') => {
+                if matches!(self.peek(), Some(b'0'..=b'9')) {
+                    return Err(self.peek_error(ErrorCode::InvalidNumber));
+                }
+            }
+            Some(b'1'..=b'9') => self.eat_digits(),
+            _ => return Err(self.error(ErrorCode::InvalidNumber)),
+        }
+
+        if self.peek() == Some(b'.') {
+            is_float = true;
+            self.eat_char();
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.peek_error(ErrorCode::InvalidNumber));
+            }
+            self.eat_digits();
+        }
+
+        if let Some(b'e' | b'E') = self.peek() {
+            is_float = true;
+            self.eat_char();
+            if let Some(b'+' | b'-') = self.peek() {
+                self.eat_char();
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.peek_error(ErrorCode::InvalidNumber));
+            }
+            self.eat_digits();
+        }
+
+        // A number must not be directly followed by a name or a dot.
+        if let Some(ch) = self.peek() {
+            if ch == b'.' || is_name_start(ch) {
+                return Err(self.peek_error(ErrorCode::InvalidNumber));
+            }
+        }
+
+        let text = std::str::from_utf8(&self.slice[start..self.index]).expect("number is ascii");
+        // An IntValue that fits in neither i64 nor u64 is an error rather than
+        // a rounded Float, so it is not silently read back as a different value.
+        let number = if is_float {
+            text.parse().map(Number::Float).ok()
+        } else if text.starts_with('-') {
+            text.parse().map(Number::NegInt).ok()
+        } else {
+            text.parse().map(Number::PosInt).ok()
+        };
+
+        number.ok_or_else(|| {
+            let code = if is_float {
+                ErrorCode::InvalidNumber
+            } else {
+                ErrorCode::IntOutOfRange { value: text.into() }
+            };
+            self.error(code)
+        })
+    }
+
+    fn eat_digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.eat_char();
+        }
+    }
+
+    fn end_seq(&mut self) -> Result<()> {
+        match self.parse_whitespace() {
+            Some(b']') => {
+                self.eat_char();
+                Ok(())
+            }
+            Some(_) => Err(self.peek_error(ErrorCode::TrailingCharacters)),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+
+    fn end_map(&mut self) -> Result<()> {
+        match self.parse_whitespace() {
+            Some(b'}') => {
+                self.eat_char();
+                Ok(())
+            }
+            Some(_) => Err(self.peek_error(ErrorCode::ExpectedObjectEnd)),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+
+    fn parse_object_colon(&mut self) -> Result<()> {
+        match self.parse_whitespace() {
+            Some(b':') => {
+                self.eat_char();
+                Ok(())
+            }
+            Some(_) => Err(self.peek_error(ErrorCode::ExpectedColon)),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+}
+
+fn as_str<'de, 's>(de: &Deserializer<'de>, slice: &'s [u8]) -> Result<&'s str> {
+    std::str::from_utf8(slice).map_err(|_| de.error(ErrorCode::InvalidUnicodeCodePoint))
+}
+
+/// Implements the spec's BlockStringValue: normalizes line endings, removes
+/// the indentation common to all lines but the first and drops leading and
+/// trailing blank lines.
+fn block_string_value(raw: &str) -> String {
+    let raw = raw.replace("\r\n", "\n").replace('\r', "\n");
+    let lines: Vec<&str> = raw.split('\n').collect();
+
+    let indent_of = |line: &str| line.len() - line.trim_start_matches([' ', '\t']).len();
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| indent_of(line) < line.len())
+        .map(|line| indent_of(line))
+        .min()
+        .unwrap_or(0);
+
+    let is_blank = |line: &&str| line.trim_start_matches([' ', '\t']).is_empty();
+    let lines: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => line,
+            _ => &line[common_indent.min(line.len())..],
+        })
+        .collect();
+    let start = lines.iter().position(|line| !is_blank(line));
+    let end = lines.iter().rposition(|line| !is_blank(line));
+
+    match (start, end) {
+        (Some(start), Some(end)) => lines[start..=end].join("\n"),
+        _ => String::new(),
+    }
+}
+
+fn is_name_start(ch: u8) -> bool {
+    ch == b'_' || ch.is_ascii_alphabetic()
+}
+
+fn is_name_continue(ch: u8) -> bool {
+    ch == b'_' || ch.is_ascii_alphanumeric()
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let peek = match self.parse_whitespace() {
+            Some(ch) => ch,
+            None => return Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        };
+
+        let value = match peek {
+            b'-' | b'0'..=b'9' => match self.parse_number()? {
+                Number::PosInt(n) => visitor.visit_u64(n),
+                Number::NegInt(n) => visitor.visit_i64(n),
+                Number::Float(n) => visitor.visit_f64(n),
+            },
+            b'"' => match self.parse_str()? {
+                Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
+                Reference::Copied(s) => visitor.visit_str(s),
+            },
+            b'[' => {
+                check_recursion! {
+                    self.eat_char();
+                    let value = visitor.visit_seq(SeqAccess { de: self });
+                }
+                match (value, self.end_seq()) {
+                    (Ok(value), Ok(())) => Ok(value),
+                    (Err(err), _) | (_, Err(err)) => Err(err),
+                }
+            }
+            b'{' => {
+                check_recursion! {
+                    self.eat_char();
+                    let value = visitor.visit_map(MapAccess { de: self });
+                }
+                match (value, self.end_map()) {
+                    (Ok(value), Ok(())) => Ok(value),
+                    (Err(err), _) | (_, Err(err)) => Err(err),
+                }
+            }
+            ch if is_name_start(ch) => match self.parse_name()? {
+                "null" => visitor.visit_unit(),
+                "true" => visitor.visit_bool(true),
+                "false" => visitor.visit_bool(false),
+                name => visitor.visit_borrowed_str(name),
+            },
+            _ => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
+        };
+
+        value.map_err(|err| self.fix_position(err))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if let Some(ch) = self.parse_whitespace() {
+            if is_name_start(ch) {
+                let start = self.index;
+                if self.parse_name()? == "null" {
+                    return visitor.visit_none();
+                }
+                self.index = start;
+            }
+        }
+
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse_whitespace() {
+            Some(ch) if is_name_start(ch) => {
+                if self.parse_name()? == "null" {
+                    visitor.visit_unit()
+                } else {
+                    Err(self.error(ErrorCode::ExpectedSomeValue))
+                }
+            }
+            Some(_) => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if name == one_of::TOKEN {
+            return visitor.visit_newtype_struct(OneOfDeserializer { de: self });
+        }
+
+        if name == value::ENUM_TOKEN {
+            return visitor.visit_newtype_struct(ValueDeserializer { de: self });
+        }
+
+        if name == raw::TOKEN {
+            self.parse_whitespace();
+            let start = self.index;
+            de::Deserializer::deserialize_ignored_any(&mut *self, de::IgnoredAny)?;
+            let slice: &'de [u8] = self.slice;
+            let raw = as_str(self, &slice[start..self.index])?;
+            return visitor
+                .visit_newtype_struct(de::value::BorrowedStrDeserializer::new(raw))
+                .map_err(|err| self.fix_position(err));
+        }
+
+        if name == variable::TOKEN {
+            match self.parse_whitespace() {
+                Some(b'$') => self.eat_char(),
+                Some(_) => return Err(self.peek_error(ErrorCode::ExpectedVariable)),
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsing)),
+            }
+            let name = self.parse_name()?;
+            return visitor
+                .visit_newtype_struct(de::value::BorrowedStrDeserializer::new(name))
+                .map_err(|err| self.fix_position(err));
+        }
+
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.parse_whitespace() {
+            Some(b'{') => {
+                check_recursion! {
+                    self.eat_char();
+                    let value = visitor.visit_enum(VariantAccess { de: self })?;
+                }
+                self.end_map()?;
+                Ok(value)
+            }
+            Some(b'"') => match self.parse_str()? {
+                Reference::Borrowed(s) => visitor.visit_enum(s.into_deserializer()),
+                Reference::Copied(s) => visitor.visit_enum(s.to_owned().into_deserializer()),
+            },
+            Some(ch) if is_name_start(ch) => {
+                let name = self.parse_name()?;
+                visitor.visit_enum(de::value::BorrowedStrDeserializer::new(name))
+            }
+            Some(_) => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
+            None => Err(self.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.parse_whitespace() == Some(b'$') {
+            self.eat_char();
+            self.parse_name()?;
+            return visitor.visit_unit();
+        }
+
+        self.deserialize_any(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+struct SeqAccess<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::SeqAccess<'de> for SeqAccess<'a, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        match self.de.parse_whitespace() {
+            Some(b']') => Ok(None),
+            Some(_) => seed.deserialize(&mut *self.de).map(Some),
+            None => Err(self.de.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+}
+
+struct MapAccess<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::MapAccess<'de> for MapAccess<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: de::DeserializeSeed<'de>,
+    {
+        match self.de.parse_whitespace() {
+            Some(b'}') => Ok(None),
+            Some(b'"') => seed.deserialize(MapKey { de: &mut *self.de }).map(Some),
+            Some(ch) if is_name_start(ch) => {
+                seed.deserialize(MapKey { de: &mut *self.de }).map(Some)
+            }
+            Some(_) => Err(self.de.peek_error(ErrorCode::ExpectedName)),
+            None => Err(self.de.peek_error(ErrorCode::EofWhileParsing)),
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        self.de.parse_object_colon()?;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+struct VariantAccess<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for VariantAccess<'a, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        match self.de.parse_whitespace() {
+            Some(b'"') => {}
+            Some(ch) if is_name_start(ch) => {}
+            Some(_) => return Err(self.de.peek_error(ErrorCode::ExpectedName)),
+            None => return Err(self.de.peek_error(ErrorCode::EofWhileParsing)),
+        }
+        let variant = seed.deserialize(MapKey { de: &mut *self.de })?;
+        self.de.parse_object_colon()?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a> de::VariantAccess<'de> for VariantAccess<'a, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
+
+/// Deserializes a `@oneOf` input object into the newtype variant whose
+/// camelCased name matches the single field of the object.
+struct OneOfDeserializer<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for OneOfDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let this = self.de;
+        match this.parse_whitespace() {
+            Some(b'{') => {}
+            Some(_) => return Err(this.peek_error(ErrorCode::ExpectedSomeValue)),
+            None => return Err(this.peek_error(ErrorCode::EofWhileParsing)),
+        }
+
+        check_recursion! {
+            this.eat_char();
+
+            match this.parse_whitespace() {
+                Some(ch) if is_name_start(ch) => {}
+                Some(_) => return Err(this.peek_error(ErrorCode::ExpectedName)),
+                None => return Err(this.peek_error(ErrorCode::EofWhileParsing)),
+            }
+            let field = this.parse_name()?;
+            let variant = variants
+                .iter()
+                .find(|variant| one_of::field_name(variant) == field)
+                .ok_or_else(|| this.fix_position(de::Error::unknown_variant(field, variants)))?;
+            this.parse_object_colon()?;
+
+            let value = visitor.visit_enum(OneOfAccess {
+                de: &mut *this,
+                variant,
+            })?;
+        }
+        this.end_map()?;
+        Ok(value)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct OneOfAccess<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+    variant: &'static str,
+}
+
+impl<'de, 'a> de::EnumAccess<'de> for OneOfAccess<'a, 'de> {
+    type Error = Error;
+    type Variant = VariantAccess<'a, 'de>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(de::value::BorrowedStrDeserializer::new(self.variant))?;
+        Ok((variant, VariantAccess { de: self.de }))
+    }
+}
+
+/// Deserializes an object key, which is either a bare GraphQL name or a quoted
+/// string.
+struct MapKey<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> de::Deserializer<'de> for MapKey<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        let value = match self.de.peek() {
+            Some(b'"') => match self.de.parse_str()? {
+                Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
+                Reference::Copied(s) => visitor.visit_str(s),
+            },
+            _ => {
+                let name = self.de.parse_name()?;
+                visitor.visit_borrowed_str(name)
+            }
+        };
+
+        value.map_err(|err| self.de.fix_position(err))
+    }
+
+    fn deserialize_bool<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        if self.de.peek() == Some(b'"') {
+            return self.deserialize_any(visitor);
+        }
+
+        let value = match self.de.parse_name()? {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            name => visitor.visit_borrowed_str(name),
+        };
+
+        value.map_err(|err| self.de.fix_position(err))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        self.de.deserialize_enum(name, variants, visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+        identifier ignored_any
+    }
+}
+
+/// Deserializes any value for `Value`, which needs to tell enum values and
+/// variables apart from strings. They are passed to the visitor as a map with
+/// a single entry, keyed by the token of the kind of name.
+struct ValueDeserializer<'a, 'de: 'a> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de, 'a> ValueDeserializer<'a, 'de> {
+    fn visit_name<V>(self, token: &'static str, name: &'de str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        visitor
+            .visit_map(de::value::MapDeserializer::new(std::iter::once((
+                token, name,
+            ))))
+            .map_err(|err| self.de.fix_position(err))
+    }
+}
+
+impl<'de, 'a> de::Deserializer<'de> for ValueDeserializer<'a, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.de.parse_whitespace() {
+            Some(b'$') => {
+                self.de.eat_char();
+                let name = self.de.parse_name()?;
+                self.visit_name(variable::TOKEN, name, visitor)
+            }
+            Some(ch) if is_name_start(ch) => {
+                let start = self.de.index;
+                match self.de.parse_name()? {
+                    "null" | "true" | "false" => {
+                        self.de.index = start;
+                        self.de.deserialize_any(visitor)
+                    }
+                    name => self.visit_name(value::ENUM_TOKEN, name, visitor),
+                }
+            }
+            _ => self.de.deserialize_any(visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+fn from_trait<'de, T>(mut de: Deserializer<'de>) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    let value = T::deserialize(&mut de)?;
+    de.end()?;
+    Ok(value)
+}
+
+pub fn from_slice<'a, T>(v: &'a [u8]) -> Result<T>
+where
+    T: Deserialize<'a>,
+{
+    from_trait(Deserializer::from_slice(v))
+}
+
+pub fn from_str<'a, T>(s: &'a str) -> Result<T>
+where
+    T: Deserialize<'a>,
+{
+    from_trait(Deserializer::from_str(s))
+}
+
+pub fn from_reader<R, T>(mut reader: R) -> Result<T>
+where
+    R: io::Read,
+    T: DeserializeOwned,
+{
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer).map_err(Error::io)?;
+    from_slice(&buffer)
+}
@@ -0,0 +1,388 @@
+//! Builders for complete GraphQL operations, such as
+//! `mutation { createUser(input: {name: "a"}) { id } }`.
+//!
+//! Argument and default values are written by the [`Serializer`], and the
+//! rest of the document goes through the same [`Formatter`], so an operation
+//! can be rendered compact or pretty.
+
+use std::borrow::Cow;
+use std::io;
+
+use serde::{Serialize, Serializer as _};
+
+use crate::error::{Error, ErrorCode, PathSegment, Result};
+use crate::{
+    format_key, is_valid_name, ArgumentsSerializer, CompactFormatter, Formatter, Map,
+    PrettyFormatter, RenamePolicy, Serializer, Value,
+};
+
+/// The kind of an [`Operation`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OperationType {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+impl OperationType {
+    fn keyword(self) -> &'static str {
+        match self {
+            OperationType::Query => "query",
+            OperationType::Mutation => "mutation",
+            OperationType::Subscription => "subscription",
+        }
+    }
+}
+
+/// A query, mutation or subscription.
+///
+/// ```
+/// # use serde_graphql_input::document::{Field, Operation};
+/// # use serde_graphql_input::Variable;
+/// let operation = Operation::mutation()
+///     .name("CreateUser")
+///     .variable("input", "CreateUserInput!")
+///     .field(
+///         Field::new("createUser")
+///             .argument("input", Variable("input"))
+///             .field("id")
+///             .field("name"),
+///     );
+///
+/// assert_eq!(
+///     "mutation CreateUser($input:CreateUserInput!){createUser(input:$input){id name}}",
+///     operation.render().unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Operation {
+    operation_type: OperationType,
+    name: Option<String>,
+    variables: Vec<VariableDefinition>,
+    selection: Vec<Field>,
+}
+
+impl Operation {
+    pub fn new(operation_type: OperationType) -> Self {
+        Operation {
+            operation_type,
+            name: None,
+            variables: Vec::new(),
+            selection: Vec::new(),
+        }
+    }
+
+    pub fn query() -> Self {
+        Operation::new(OperationType::Query)
+    }
+
+    pub fn mutation() -> Self {
+        Operation::new(OperationType::Mutation)
+    }
+
+    pub fn subscription() -> Self {
+        Operation::new(OperationType::Subscription)
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Declares the variable `$name` of type `ty`, e.g. `"[ID!]!"`.
+    pub fn variable(mut self, name: impl Into<String>, ty: impl Into<String>) -> Self {
+        self.variables.push(VariableDefinition {
+            name: name.into(),
+            ty: ty.into(),
+            default: None,
+        });
+        self
+    }
+
+    /// Declares the variable `$name` of type `ty`, used when the request does
+    /// not provide it. The default must be constant, so rendering fails if it
+    /// contains a variable.
+    pub fn variable_with_default(
+        mut self,
+        name: impl Into<String>,
+        ty: impl Into<String>,
+        default: impl Into<Value>,
+    ) -> Self {
+        self.variables.push(VariableDefinition {
+            name: name.into(),
+            ty: ty.into(),
+            default: Some(default.into()),
+        });
+        self
+    }
+
+    /// Selects a field of the root type.
+    pub fn field(mut self, field: impl Into<Field>) -> Self {
+        self.selection.push(field.into());
+        self
+    }
+
+    pub fn to_writer_with_formatter<W, F>(&self, writer: W, formatter: F) -> Result<()>
+    where
+        W: io::Write,
+        F: Formatter,
+    {
+        let mut ser = Serializer::with_formatter(writer, formatter);
+        write_operation(&mut ser, self)
+    }
+
+    pub fn render(&self) -> Result<String> {
+        self.render_with_formatter(CompactFormatter)
+    }
+
+    pub fn render_pretty(&self) -> Result<String> {
+        self.render_with_formatter(PrettyFormatter::new())
+    }
+
+    fn render_with_formatter<F>(&self, formatter: F) -> Result<String>
+    where
+        F: Formatter,
+    {
+        let mut writer = Vec::with_capacity(128);
+        self.to_writer_with_formatter(&mut writer, formatter)?;
+        let string = unsafe { String::from_utf8_unchecked(writer) };
+
+        Ok(string)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct VariableDefinition {
+    name: String,
+    ty: String,
+    default: Option<Value>,
+}
+
+/// A selected field, with optional alias, arguments and sub-selection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Field {
+    alias: Option<String>,
+    name: String,
+    arguments: Map,
+    selection: Vec<Field>,
+}
+
+impl Field {
+    pub fn new(name: impl Into<String>) -> Self {
+        Field {
+            alias: None,
+            name: name.into(),
+            arguments: Map::new(),
+            selection: Vec::new(),
+        }
+    }
+
+    pub fn alias(mut self, alias: impl Into<String>) -> Self {
+        self.alias = Some(alias.into());
+        self
+    }
+
+    /// Passes `value` as the argument `name`. Use [`to_value`](crate::to_value)
+    /// or [`graphql_input!`](crate::graphql_input) for anything that is not
+    /// already a [`Value`].
+    pub fn argument(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.arguments.insert(name.into(), value.into());
+        self
+    }
+
+    /// Selects a field of this field's type.
+    pub fn field(mut self, field: impl Into<Field>) -> Self {
+        self.selection.push(field.into());
+        self
+    }
+}
+
+impl From<&str> for Field {
+    fn from(name: &str) -> Self {
+        Field::new(name)
+    }
+}
+
+impl From<String> for Field {
+    fn from(name: String) -> Self {
+        Field::new(name)
+    }
+}
+
+fn write_operation<W, F>(ser: &mut Serializer<W, F>, operation: &Operation) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    if operation.selection.is_empty() {
+        return Err(Error::from_code(ErrorCode::EmptySelectionSet));
+    }
+
+    ser.formatter
+        .write_string(&mut ser.writer, operation.operation_type.keyword())
+        .map_err(Error::io)?;
+
+    if let Some(name) = &operation.name {
+        ser.formatter
+            .write_string(&mut ser.writer, " ")
+            .map_err(Error::io)?;
+        write_name(ser, name)?;
+    }
+
+    if !operation.variables.is_empty() {
+        ser.formatter
+            .begin_arguments(&mut ser.writer)
+            .map_err(Error::io)?;
+        for (index, variable) in operation.variables.iter().enumerate() {
+            write_variable_definition(ser, variable, index == 0)?;
+        }
+        ser.formatter
+            .end_arguments(&mut ser.writer)
+            .map_err(Error::io)?;
+    }
+
+    write_selection_set(ser, &operation.selection)
+}
+
+fn write_variable_definition<W, F>(
+    ser: &mut Serializer<W, F>,
+    variable: &VariableDefinition,
+    first: bool,
+) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    if !is_valid_name(&variable.name) {
+        return Err(Error::invalid_name(&variable.name));
+    }
+    if !is_valid_type(&variable.ty) {
+        return Err(Error::from_code(ErrorCode::InvalidType(
+            variable.ty.as_str().into(),
+        )));
+    }
+    if let Some(name) = variable.default.as_ref().and_then(find_variable) {
+        let path = PathSegment::Field(Cow::Owned(format!("${}", variable.name)));
+        return Err(
+            Error::from_code(ErrorCode::VariableInDefaultValue(name.into())).with_path(&[path]),
+        );
+    }
+
+    ser.formatter
+        .begin_object_key(&mut ser.writer, first)
+        .map_err(Error::io)?;
+    ser.formatter
+        .write_variable(&mut ser.writer, &variable.name)
+        .map_err(Error::io)?;
+    ser.formatter
+        .end_object_key(&mut ser.writer)
+        .map_err(Error::io)?;
+    ser.formatter
+        .begin_object_value(&mut ser.writer)
+        .map_err(Error::io)?;
+    ser.formatter
+        .write_string(&mut ser.writer, &variable.ty)
+        .map_err(Error::io)?;
+
+    if let Some(default) = &variable.default {
+        ser.formatter
+            .begin_default_value(&mut ser.writer)
+            .map_err(Error::io)?;
+        ser.path.push(PathSegment::Field(Cow::Owned(format!(
+            "${}",
+            variable.name
+        ))));
+        default
+            .serialize(&mut *ser)
+            .map_err(|err| err.with_path(&ser.path))?;
+        ser.path.pop();
+    }
+
+    ser.formatter
+        .end_object_value(&mut ser.writer)
+        .map_err(Error::io)
+}
+
+fn write_selection_set<W, F>(ser: &mut Serializer<W, F>, selection: &[Field]) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    ser.formatter
+        .begin_selection_set(&mut ser.writer)
+        .map_err(Error::io)?;
+    for (index, field) in selection.iter().enumerate() {
+        ser.formatter
+            .begin_selection(&mut ser.writer, index == 0)
+            .map_err(Error::io)?;
+        write_field(ser, field)?;
+    }
+    ser.formatter
+        .end_selection_set(&mut ser.writer)
+        .map_err(Error::io)
+}
+
+fn write_field<W, F>(ser: &mut Serializer<W, F>, field: &Field) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    if let Some(alias) = &field.alias {
+        write_name(ser, alias)?;
+        ser.formatter
+            .end_object_key(&mut ser.writer)
+            .map_err(Error::io)?;
+        ser.formatter
+            .begin_object_value(&mut ser.writer)
+            .map_err(Error::io)?;
+    }
+    write_name(ser, &field.name)?;
+
+    let key = field.alias.as_ref().unwrap_or(&field.name);
+    ser.path.push(PathSegment::Field(Cow::Owned(key.clone())));
+
+    ArgumentsSerializer::new(&mut *ser)
+        .collect_map(&field.arguments)
+        .map_err(|err| err.with_path(&ser.path))?;
+    if !field.selection.is_empty() {
+        write_selection_set(ser, &field.selection)?;
+    }
+
+    ser.path.pop();
+    Ok(())
+}
+
+fn write_name<W, F>(ser: &mut Serializer<W, F>, name: &str) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    format_key(
+        &mut ser.writer,
+        &mut ser.formatter,
+        name,
+        RenamePolicy::AsIs,
+    )
+    .map_err(|err| err.with_path(&ser.path))
+}
+
+/// Returns the name of the first variable in `value`, which makes it unusable
+/// as a default value.
+fn find_variable(value: &Value) -> Option<&str> {
+    match value {
+        Value::Variable(name) => Some(name),
+        Value::List(values) => values.iter().find_map(find_variable),
+        Value::Object(fields) => fields.values().find_map(find_variable),
+        _ => None,
+    }
+}
+
+/// Checks that `ty` is a GraphQL type reference such as `ID`, `[Int!]` or
+/// `[[String]!]!`, without whitespace.
+fn is_valid_type(ty: &str) -> bool {
+    let ty = ty.strip_suffix('!').unwrap_or(ty);
+    match ty.strip_prefix('[') {
+        Some(inner) => inner.strip_suffix(']').is_some_and(is_valid_type),
+        None => is_valid_name(ty),
+    }
+}
@@ -1,27 +1,309 @@
-use std::{fmt::Display, io, result};
+use std::{borrow::Cow, fmt::Display, io, result};
 pub struct Error {
     err: Box<ErrorImpl>,
 }
 
 impl Error {
+    pub(crate) fn from_code(code: ErrorCode) -> Self {
+        Error {
+            err: Box::new(ErrorImpl {
+                code,
+                line: 0,
+                column: 0,
+                path: Vec::new(),
+            }),
+        }
+    }
+
     pub(crate) fn io(error: io::Error) -> Self {
+        Error::from_code(ErrorCode::Io(error))
+    }
+
+    pub(crate) fn invalid_name(name: &str) -> Self {
+        Error::from_code(ErrorCode::InvalidName(name.into()))
+    }
+
+    pub(crate) fn syntax(code: ErrorCode, line: usize, column: usize) -> Self {
         Error {
             err: Box::new(ErrorImpl {
-                code: ErrorCode::Io(error),
+                code,
+                line,
+                column,
+                path: Vec::new(),
             }),
         }
     }
+
+    pub(crate) fn fix_position<F>(self, f: F) -> Self
+    where
+        F: FnOnce(ErrorCode) -> Error,
+    {
+        if self.err.line == 0 {
+            f(self.err.code)
+        } else {
+            self
+        }
+    }
+
+    /// Records where in the value being serialized the error occurred. Only
+    /// the innermost path is kept, as errors bubble up through every parent.
+    pub(crate) fn with_path(mut self, path: &[PathSegment]) -> Self {
+        if self.err.path.is_empty() && self.err.line == 0 {
+            self.err.path = path.to_vec();
+        }
+        self
+    }
+
+    /// Categorizes the cause of this error.
+    pub fn classify(&self) -> Category {
+        match &self.err.code {
+            ErrorCode::Message(_)
+            | ErrorCode::InvalidName(_)
+            | ErrorCode::InvalidEnumValue(_)
+            | ErrorCode::KeyMustBeAName { .. }
+            | ErrorCode::IntOutOfRange { .. }
+            | ErrorCode::BytesNotAllowed
+            | ErrorCode::FloatNotFinite { .. }
+            | ErrorCode::OneOfExpectedEnum { .. }
+            | ErrorCode::ArgumentsExpectedObject { .. }
+            | ErrorCode::InvalidType(_)
+            | ErrorCode::EmptySelectionSet
+            | ErrorCode::VariableInDefaultValue(_)
+            | ErrorCode::ExpectedString { .. }
+            | ErrorCode::OneOfVariantFields { .. } => Category::Data,
+            ErrorCode::Io(_) => Category::Io,
+            #[cfg(feature = "json")]
+            ErrorCode::Json(err) => match err.classify() {
+                serde_json::error::Category::Io => Category::Io,
+                serde_json::error::Category::Syntax => Category::Syntax,
+                serde_json::error::Category::Data => Category::Data,
+                serde_json::error::Category::Eof => Category::Eof,
+            },
+            ErrorCode::EofWhileParsing => Category::Eof,
+            ErrorCode::ExpectedColon
+            | ErrorCode::ExpectedName
+            | ErrorCode::ExpectedObjectEnd
+            | ErrorCode::ExpectedSomeValue
+            | ErrorCode::ExpectedVariable
+            | ErrorCode::InvalidEscape
+            | ErrorCode::InvalidNumber
+            | ErrorCode::InvalidUnicodeCodePoint
+            | ErrorCode::ControlCharacterWhileParsingString
+            | ErrorCode::TrailingCharacters
+            | ErrorCode::RecursionLimitExceeded => Category::Syntax,
+        }
+    }
+
+    /// Returns true if this error was caused by a failure to read or write
+    /// bytes on an I/O stream.
+    pub fn is_io(&self) -> bool {
+        self.classify() == Category::Io
+    }
+
+    /// Returns true if this error was caused by input that is not valid
+    /// GraphQL syntax.
+    pub fn is_syntax(&self) -> bool {
+        self.classify() == Category::Syntax
+    }
+
+    /// Returns true if this error was caused by a value that cannot be
+    /// represented as GraphQL input, or input that does not match the type
+    /// being deserialized.
+    pub fn is_data(&self) -> bool {
+        self.classify() == Category::Data
+    }
+
+    /// Returns true if this error was caused by prematurely reaching the end
+    /// of the input.
+    pub fn is_eof(&self) -> bool {
+        self.classify() == Category::Eof
+    }
+
+    /// The kind of the underlying I/O error, if this error was caused by one.
+    pub fn io_error_kind(&self) -> Option<io::ErrorKind> {
+        match &self.err.code {
+            ErrorCode::Io(err) => Some(err.kind()),
+            #[cfg(feature = "json")]
+            ErrorCode::Json(err) => err.io_error_kind(),
+            _ => None,
+        }
+    }
+
+    /// Path to the value that failed to serialize, outermost segment first.
+    /// Empty if the error occurred at the root or during parsing.
+    pub fn path(&self) -> &[PathSegment] {
+        &self.err.path
+    }
+
+    /// One-based line of the input where the error occurred, or 0 if the error
+    /// did not originate from parsing.
+    pub fn line(&self) -> usize {
+        self.err.line
+    }
+
+    /// One-based column of the input where the error occurred, or 0 if the
+    /// error did not originate from parsing.
+    pub fn column(&self) -> usize {
+        self.err.column
+    }
 }
 
 pub type Result<T> = result::Result<T, Error>;
 
+/// Categorizes the cause of an [`Error`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Category {
+    /// Failure to read or write bytes on an I/O stream.
+    Io,
+    /// Input that is not syntactically valid GraphQL.
+    Syntax,
+    /// A value that cannot be represented as GraphQL input, or input that is
+    /// valid GraphQL but does not match the type being deserialized.
+    Data,
+    /// The input ended before a complete value was parsed.
+    Eof,
+}
+
 struct ErrorImpl {
     code: ErrorCode,
+    line: usize,
+    column: usize,
+    path: Vec<PathSegment>,
+}
+
+/// A step in the path to a value, as reported by [`Error::path`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathSegment {
+    /// A struct field or map key.
+    Field(Cow<'static, str>),
+    /// An element of a list.
+    Index(usize),
+    /// The payload of an enum variant.
+    Variant(Cow<'static, str>),
+}
+
+struct DisplayPath<'a>(&'a [PathSegment]);
+
+impl<'a> Display for DisplayPath<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(name) | PathSegment::Variant(name) => {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    f.write_str(name)?;
+                }
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
 }
 
 pub(crate) enum ErrorCode {
     Message(Box<str>),
     Io(io::Error),
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    InvalidName(Box<str>),
+    InvalidEnumValue(Box<str>),
+    KeyMustBeAName {
+        kind: Kind,
+    },
+    IntOutOfRange {
+        value: Box<str>,
+    },
+    BytesNotAllowed,
+    FloatNotFinite {
+        value: f64,
+    },
+    OneOfExpectedEnum {
+        kind: Kind,
+    },
+    ArgumentsExpectedObject {
+        kind: Kind,
+    },
+    InvalidType(Box<str>),
+    EmptySelectionSet,
+    VariableInDefaultValue(Box<str>),
+    ExpectedString {
+        context: &'static str,
+        kind: Kind,
+    },
+    OneOfVariantFields {
+        variant: Box<str>,
+        fields: usize,
+    },
+
+    EofWhileParsing,
+    ExpectedColon,
+    ExpectedName,
+    ExpectedObjectEnd,
+    ExpectedSomeValue,
+    ExpectedVariable,
+    InvalidEscape,
+    InvalidNumber,
+    InvalidUnicodeCodePoint,
+    ControlCharacterWhileParsingString,
+    TrailingCharacters,
+    RecursionLimitExceeded,
+}
+
+/// The Serde data model type that was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Kind {
+    Bool,
+    Integer,
+    I128,
+    U128,
+    Float,
+    Char,
+    String,
+    Bytes,
+    None,
+    Some,
+    Unit,
+    UnitStruct,
+    UnitVariant,
+    NewtypeStruct,
+    NewtypeVariant,
+    Seq,
+    Tuple,
+    TupleStruct,
+    TupleVariant,
+    Map,
+    Struct,
+    StructVariant,
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Kind::Bool => "boolean",
+            Kind::Integer => "integer",
+            Kind::I128 => "i128",
+            Kind::U128 => "u128",
+            Kind::Float => "float",
+            Kind::Char => "char",
+            Kind::String => "string",
+            Kind::Bytes => "bytes",
+            Kind::None => "none",
+            Kind::Some => "option",
+            Kind::Unit => "unit",
+            Kind::UnitStruct => "unit struct",
+            Kind::UnitVariant => "unit variant",
+            Kind::NewtypeStruct => "newtype struct",
+            Kind::NewtypeVariant => "newtype variant",
+            Kind::Seq => "sequence",
+            Kind::Tuple => "tuple",
+            Kind::TupleStruct => "tuple struct",
+            Kind::TupleVariant => "tuple variant",
+            Kind::Map => "map",
+            Kind::Struct => "struct",
+            Kind::StructVariant => "struct variant",
+        })
+    }
 }
 
 impl serde::ser::Error for Error {
@@ -32,6 +314,25 @@ impl serde::ser::Error for Error {
         Error {
             err: Box::new(ErrorImpl {
                 code: ErrorCode::Message(msg.to_string().into_boxed_str()),
+                line: 0,
+                column: 0,
+                path: Vec::new(),
+            }),
+        }
+    }
+}
+
+impl serde::de::Error for Error {
+    fn custom<T>(msg: T) -> Self
+    where
+        T: Display,
+    {
+        Error {
+            err: Box::new(ErrorImpl {
+                code: ErrorCode::Message(msg.to_string().into_boxed_str()),
+                line: 0,
+                column: 0,
+                path: Vec::new(),
             }),
         }
     }
@@ -39,13 +340,37 @@ impl serde::ser::Error for Error {
 
 impl serde::ser::StdError for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match &self.err.code {
+            ErrorCode::Io(err) => Some(err),
+            #[cfg(feature = "json")]
+            ErrorCode::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    /// Unwraps the underlying I/O error, or wraps data, syntax and EOF errors
+    /// as `InvalidData` and `UnexpectedEof` respectively.
+    fn from(err: Error) -> Self {
+        match err.err.code {
+            ErrorCode::Io(err) => err,
+            #[cfg(feature = "json")]
+            ErrorCode::Json(err) => err.into(),
+            _ => match err.classify() {
+                Category::Io => unreachable!(),
+                Category::Syntax | Category::Data => {
+                    io::Error::new(io::ErrorKind::InvalidData, err)
+                }
+                Category::Eof => io::Error::new(io::ErrorKind::UnexpectedEof, err),
+            },
+        }
     }
 }
 
 impl std::fmt::Debug for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error({:?})", self.err.code.to_string())
+        write!(f, "Error({:?})", self.err.to_string())
     }
 }
 
@@ -57,7 +382,17 @@ impl Display for Error {
 
 impl Display for ErrorImpl {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.code)
+        if !self.path.is_empty() {
+            write!(f, "at {}: {}", DisplayPath(&self.path), self.code)
+        } else if self.line == 0 {
+            write!(f, "{}", self.code)
+        } else {
+            write!(
+                f,
+                "{} at line {} column {}",
+                self.code, self.line, self.column
+            )
+        }
     }
 }
 
@@ -66,6 +401,59 @@ impl Display for ErrorCode {
         match self {
             ErrorCode::Message(m) => f.write_str(m),
             ErrorCode::Io(err) => Display::fmt(err, f),
+            #[cfg(feature = "json")]
+            ErrorCode::Json(err) => Display::fmt(err, f),
+            ErrorCode::InvalidName(name) => write!(f, "`{name}` is not a valid GraphQL name"),
+            ErrorCode::InvalidEnumValue(name) => {
+                write!(f, "`{name}` is not a valid GraphQL enum value")
+            }
+            ErrorCode::KeyMustBeAName { kind } => {
+                write!(f, "key must be a GraphQL name, found {kind}")
+            }
+            ErrorCode::IntOutOfRange { value } => {
+                write!(f, "`{value}` is out of range for a GraphQL Int")
+            }
+            ErrorCode::BytesNotAllowed => {
+                f.write_str("bytes are not allowed by the serializer's bytes policy")
+            }
+            ErrorCode::FloatNotFinite { value } => {
+                write!(f, "`{value}` is not a finite float")
+            }
+            ErrorCode::OneOfExpectedEnum { kind } => {
+                write!(f, "oneOf input must be an enum, found {kind}")
+            }
+            ErrorCode::ArgumentsExpectedObject { kind } => {
+                write!(f, "arguments must be a struct or map, found {kind}")
+            }
+            ErrorCode::InvalidType(ty) => write!(f, "`{ty}` is not a valid GraphQL type"),
+            ErrorCode::EmptySelectionSet => {
+                f.write_str("an operation must select at least one field")
+            }
+            ErrorCode::VariableInDefaultValue(name) => {
+                write!(f, "default values must be constant, found `${name}`")
+            }
+            ErrorCode::ExpectedString { context, kind } => {
+                write!(f, "{context} must be a string, found {kind}")
+            }
+            ErrorCode::OneOfVariantFields { variant, fields } => write!(
+                f,
+                "oneOf variant `{variant}` must be a newtype variant, found {fields} field{}",
+                if *fields == 1 { "" } else { "s" }
+            ),
+            ErrorCode::EofWhileParsing => f.write_str("EOF while parsing a value"),
+            ErrorCode::ExpectedColon => f.write_str("expected `:`"),
+            ErrorCode::ExpectedName => f.write_str("expected a name"),
+            ErrorCode::ExpectedObjectEnd => f.write_str("expected `}`"),
+            ErrorCode::ExpectedSomeValue => f.write_str("expected value"),
+            ErrorCode::ExpectedVariable => f.write_str("expected variable"),
+            ErrorCode::InvalidEscape => f.write_str("invalid escape"),
+            ErrorCode::InvalidNumber => f.write_str("invalid number"),
+            ErrorCode::InvalidUnicodeCodePoint => f.write_str("invalid unicode code point"),
+            ErrorCode::ControlCharacterWhileParsingString => {
+                f.write_str("control character (\\u0000-\\u001F) found while parsing a string")
+            }
+            ErrorCode::TrailingCharacters => f.write_str("trailing characters"),
+            ErrorCode::RecursionLimitExceeded => f.write_str("recursion limit exceeded"),
         }
     }
 }
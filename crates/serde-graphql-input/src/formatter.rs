@@ -1,30 +1,84 @@
 use std::io;
 
+use crate::error::{Error, ErrorCode, Result};
+use crate::{RenamePolicy, UnicodePolicy};
+
 mod compact;
 mod pretty;
 
 pub use compact::*;
 pub use pretty::*;
 
-pub(crate) fn format_key<W, F>(writer: &mut W, formatter: &mut F, value: &str) -> io::Result<()>
+pub(crate) fn format_key<W, F>(
+    writer: &mut W,
+    formatter: &mut F,
+    value: &str,
+    rename: RenamePolicy,
+) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    let value = rename.apply(value);
+    if !is_valid_name(&value) {
+        return Err(Error::invalid_name(&value));
+    }
+
+    formatter.write_string(writer, &value).map_err(Error::io)
+}
+
+/// Like [`format_key`], but for an enum value, which is a name other than
+/// `true`, `false` or `null`. Those would be read back as a boolean or null.
+pub(crate) fn format_enum_value<W, F>(
+    writer: &mut W,
+    formatter: &mut F,
+    value: &str,
+    rename: RenamePolicy,
+) -> Result<()>
 where
     W: io::Write,
     F: Formatter,
 {
-    formatter.write_string(writer, value)
+    let value = rename.apply(value);
+    check_enum_value(&value)?;
+
+    formatter.write_string(writer, &value).map_err(Error::io)
+}
+
+/// Checks that `value` is a GraphQL `EnumValue`: a name other than `true`,
+/// `false` or `null`.
+pub(crate) fn check_enum_value(value: &str) -> Result<()> {
+    if !is_valid_name(value) {
+        return Err(Error::invalid_name(value));
+    }
+    if let "true" | "false" | "null" = value {
+        return Err(Error::from_code(ErrorCode::InvalidEnumValue(value.into())));
+    }
+    Ok(())
+}
+
+/// Checks that `value` is a GraphQL `Name`, i.e. matches `/[_A-Za-z][_0-9A-Za-z]*/`.
+pub(crate) fn is_valid_name(value: &str) -> bool {
+    let mut bytes = value.bytes();
+    match bytes.next() {
+        Some(b) if b == b'_' || b.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+    bytes.all(|b| b == b'_' || b.is_ascii_alphanumeric())
 }
 
 pub(crate) fn format_escaped_str<W, F>(
     writer: &mut W,
     formatter: &mut F,
     value: &str,
+    unicode: UnicodePolicy,
 ) -> io::Result<()>
 where
     W: io::Write,
     F: Formatter,
 {
     formatter.begin_string(writer)?;
-    format_escaped_str_contents(writer, formatter, value)?;
+    format_escaped_str_contents(writer, formatter, value, unicode)?;
     formatter.end_string(writer)
 }
 
@@ -32,6 +86,7 @@ fn format_escaped_str_contents<W, F>(
     writer: &mut W,
     formatter: &mut F,
     value: &str,
+    unicode: UnicodePolicy,
 ) -> io::Result<()>
 where
     W: io::Write,
@@ -40,10 +95,13 @@ where
     let bytes = value.as_bytes();
 
     let mut start = 0;
+    let mut i = 0;
 
-    for (i, &byte) in bytes.iter().enumerate() {
+    while i < bytes.len() {
+        let byte = bytes[i];
         let escape = ESCAPE[byte as usize];
-        if escape == 0 {
+        if escape == 0 && (byte.is_ascii() || unicode == UnicodePolicy::Raw) {
+            i += 1;
             continue;
         }
 
@@ -51,10 +109,26 @@ where
             formatter.write_string_fragment(writer, &value[start..i])?;
         }
 
-        let char_escape = CharEscape::from_escape_table(escape, byte);
-        formatter.write_char_escape(writer, char_escape)?;
+        if escape != 0 {
+            let char_escape = CharEscape::from_escape_table(escape, byte);
+            formatter.write_char_escape(writer, char_escape)?;
+            i += 1;
+        } else {
+            // Only reached on the first byte of a character, as every
+            // non-ascii character is escaped in full.
+            let ch = value[i..].chars().next().expect("char boundary");
+            match unicode {
+                UnicodePolicy::EscapeUtf16 => {
+                    for &unit in ch.encode_utf16(&mut [0; 2]).iter() {
+                        formatter.write_char_escape(writer, CharEscape::Utf16(unit))?;
+                    }
+                }
+                _ => formatter.write_char_escape(writer, CharEscape::CodePoint(ch))?,
+            }
+            i += ch.len_utf8();
+        }
 
-        start = i + 1;
+        start = i;
     }
 
     if start == bytes.len() {
@@ -64,6 +138,70 @@ where
     formatter.write_string_fragment(writer, &value[start..])
 }
 
+/// Writes `value` as a `"""` block string. Only strings accepted by
+/// `is_block_string_safe` read back as the same value.
+pub(crate) fn format_block_str<W, F>(
+    writer: &mut W,
+    formatter: &mut F,
+    value: &str,
+) -> io::Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    formatter.begin_block_string(writer)?;
+
+    let mut rest = value;
+    while let Some(i) = rest.find(r#"""""#) {
+        formatter.write_string_fragment(writer, &rest[..i])?;
+        formatter.write_string_fragment(writer, r#"\""""#)?;
+        rest = &rest[i + 3..];
+    }
+    formatter.write_string_fragment(writer, rest)?;
+
+    formatter.end_block_string(writer)
+}
+
+/// Whether `value` survives being written as a block string. Reading a block
+/// string normalizes line endings, strips the indentation common to all lines
+/// and drops leading and trailing blank lines, and it cannot contain escaped
+/// control characters.
+pub(crate) fn is_block_string_safe(value: &str) -> bool {
+    let mut is_empty_line = true;
+    let mut has_indent = false;
+    let mut has_common_indent = true;
+    let mut seen_non_empty_line = false;
+
+    for byte in value.bytes() {
+        match byte {
+            b'\n' => {
+                if is_empty_line && !seen_non_empty_line {
+                    // Leading blank line.
+                    return false;
+                }
+                seen_non_empty_line = true;
+                is_empty_line = true;
+                has_indent = false;
+            }
+            b'\t' | b' ' => has_indent |= is_empty_line,
+            // Line endings are normalized and escapes are not allowed.
+            0x00..=0x1F => return false,
+            _ => {
+                has_common_indent &= has_indent;
+                is_empty_line = false;
+            }
+        }
+    }
+
+    if is_empty_line {
+        // Trailing blank line.
+        return false;
+    }
+
+    // Indentation shared by every line would be stripped.
+    !(has_common_indent && seen_non_empty_line)
+}
+
 const BB: u8 = b'b'; // \x08
 const TT: u8 = b't'; // \x09
 const NN: u8 = b'n'; // \x0A
@@ -117,6 +255,13 @@ pub enum CharEscape {
     /// An escaped ASCII plane control character (usually escaped as
     /// `\u00XX` where `XX` are two hex characters)
     AsciiControl(u8),
+    /// An escaped UTF-16 code unit of a non-ASCII character (usually escaped
+    /// as `\uXXXX`, with characters outside the Basic Multilingual Plane
+    /// written as a surrogate pair)
+    Utf16(u16),
+    /// An escaped non-ASCII character (usually escaped as `\u{X}` where `X`
+    /// is its code point in hex)
+    CodePoint(char),
 }
 
 impl CharEscape {
@@ -173,6 +318,15 @@ pub trait Formatter {
         writer.write_all(s.as_bytes())
     }
 
+    fn write_i128<W>(&mut self, writer: &mut W, value: i128) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        let mut buffer = itoa::Buffer::new();
+        let s = buffer.format(value);
+        writer.write_all(s.as_bytes())
+    }
+
     fn write_u8<W>(&mut self, writer: &mut W, value: u8) -> io::Result<()>
     where
         W: ?Sized + io::Write,
@@ -209,18 +363,37 @@ pub trait Formatter {
         writer.write_all(s.as_bytes())
     }
 
+    fn write_u128<W>(&mut self, writer: &mut W, value: u128) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        let mut buffer = itoa::Buffer::new();
+        let s = buffer.format(value);
+        writer.write_all(s.as_bytes())
+    }
+
+    /// Writes the shortest representation that round-trips, always with a
+    /// decimal point or exponent so it is never read back as an `Int`. The
+    /// value must be finite.
     fn write_f32<W>(&mut self, writer: &mut W, value: f32) -> io::Result<()>
     where
         W: ?Sized + io::Write,
     {
-        writer.write_all(format!("{value}").as_bytes())
+        let mut buffer = ryu::Buffer::new();
+        let s = buffer.format_finite(value);
+        writer.write_all(s.as_bytes())
     }
 
+    /// Writes the shortest representation that round-trips, always with a
+    /// decimal point or exponent so it is never read back as an `Int`. The
+    /// value must be finite.
     fn write_f64<W>(&mut self, writer: &mut W, value: f64) -> io::Result<()>
     where
         W: ?Sized + io::Write,
     {
-        writer.write_all(format!("{value}").as_bytes())
+        let mut buffer = ryu::Buffer::new();
+        let s = buffer.format_finite(value);
+        writer.write_all(s.as_bytes())
     }
 
     fn write_bool<W>(&mut self, writer: &mut W, value: bool) -> io::Result<()>
@@ -239,6 +412,9 @@ pub trait Formatter {
         writer.write_all(b"null")
     }
 
+    /// Never called: GraphQL has no character literals, so the `Serializer`
+    /// writes a `char` as a string.
+    #[deprecated(note = "chars are written as strings; this method is never called")]
     fn write_char<W>(&mut self, writer: &mut W, value: char) -> io::Result<()>
     where
         W: ?Sized + io::Write,
@@ -246,6 +422,15 @@ pub trait Formatter {
         writer.write_all(format!(r#"'{}'"#, value).as_bytes())
     }
 
+    /// Writes a variable reference such as `$id`. The name is already validated.
+    fn write_variable<W>(&mut self, writer: &mut W, name: &str) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"$")?;
+        writer.write_all(name.as_bytes())
+    }
+
     fn begin_object<W>(&mut self, writer: &mut W) -> io::Result<()>
     where
         W: ?Sized + io::Write,
@@ -295,6 +480,76 @@ pub trait Formatter {
         writer.write_all(b"\"")
     }
 
+    /// Called before the entries of a field argument list.
+    fn begin_arguments<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"(")
+    }
+
+    /// Called after the entries of a field argument list.
+    fn end_arguments<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b")")
+    }
+
+    /// Called before the fields selected by an operation or field.
+    fn begin_selection_set<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"{")
+    }
+
+    /// Called after the fields selected by an operation or field.
+    fn end_selection_set<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"}")
+    }
+
+    /// Called before each field of a selection set.
+    fn begin_selection<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        if first {
+            Ok(())
+        } else {
+            writer.write_all(b" ")
+        }
+    }
+
+    /// Called between the type of a variable definition and its default value.
+    fn begin_default_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"=")
+    }
+
+    /// Called before the contents of a block string. Must end with a line
+    /// break, which is dropped when the string is read back.
+    fn begin_block_string<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"\"\"\"\n")
+    }
+
+    /// Called after the contents of a block string. Must start with a line
+    /// break, which is dropped when the string is read back.
+    fn end_block_string<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"\n\"\"\"")
+    }
+
     fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
     where
         W: ?Sized + io::Write,
@@ -381,6 +636,8 @@ pub trait Formatter {
                 ];
                 return writer.write_all(bytes);
             }
+            Utf16(unit) => return write!(writer, "\\u{unit:04x}"),
+            CodePoint(ch) => return write!(writer, "\\u{{{:x}}}", ch as u32),
         };
 
         writer.write_all(s)
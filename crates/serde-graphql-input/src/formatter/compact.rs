@@ -1,5 +1,38 @@
-use crate::Formatter;
+use std::io;
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::{Formatter, Serializer};
 
 #[derive(Clone, Debug)]
 pub struct CompactFormatter;
 impl Formatter for CompactFormatter {}
+
+pub fn to_writer<W, T>(writer: W, value: &T) -> Result<()>
+where
+    W: io::Write,
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::new(writer);
+    value.serialize(&mut ser)
+}
+
+pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    to_writer(&mut writer, value)?;
+    Ok(writer)
+}
+
+pub fn to_string<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let vec = to_vec(value)?;
+    let string = unsafe { String::from_utf8_unchecked(vec) };
+
+    Ok(string)
+}
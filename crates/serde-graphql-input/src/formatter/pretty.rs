@@ -1,9 +1,11 @@
+use std::io;
+
 use serde::Serialize;
 
 use crate::error::Result;
 use crate::{Formatter, Serializer};
 
-#[allow(dead_code)]
+#[derive(Clone, Debug)]
 pub struct PrettyFormatter<'a> {
     current_indent: usize,
     has_value: bool,
@@ -12,7 +14,7 @@ pub struct PrettyFormatter<'a> {
 
 impl<'a> PrettyFormatter<'a> {
     pub fn new() -> Self {
-        PrettyFormatter::with_indent(b" ")
+        PrettyFormatter::with_indent(b"  ")
     }
 
     pub fn with_indent(indent: &'a [u8]) -> Self {
@@ -30,18 +32,183 @@ impl<'a> Default for PrettyFormatter<'a> {
     }
 }
 
-impl<'a> Formatter for PrettyFormatter<'a> {}
+impl<'a> Formatter for PrettyFormatter<'a> {
+    fn begin_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"{")
+    }
+
+    fn end_object<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent -= 1;
 
-pub fn to_string_pretty<T>(value: &T) -> Result<String>
+        if self.has_value {
+            writer.write_all(b"\n")?;
+            indent(writer, self.current_indent, self.indent)?;
+        }
+
+        writer.write_all(b"}")
+    }
+
+    fn begin_arguments<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"(")
+    }
+
+    fn end_arguments<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent -= 1;
+
+        if self.has_value {
+            writer.write_all(b"\n")?;
+            indent(writer, self.current_indent, self.indent)?;
+        }
+
+        writer.write_all(b")")
+    }
+
+    fn begin_selection_set<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent += 1;
+        writer.write_all(b" {")
+    }
+
+    fn end_selection_set<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent -= 1;
+        writer.write_all(b"\n")?;
+        indent(writer, self.current_indent, self.indent)?;
+        writer.write_all(b"}")
+    }
+
+    fn begin_selection<W>(&mut self, writer: &mut W, _first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"\n")?;
+        indent(writer, self.current_indent, self.indent)
+    }
+
+    fn begin_default_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b" = ")
+    }
+
+    fn begin_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent += 1;
+        self.has_value = false;
+        writer.write_all(b"[")
+    }
+
+    fn end_array<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.current_indent -= 1;
+
+        if self.has_value {
+            writer.write_all(b"\n")?;
+            indent(writer, self.current_indent, self.indent)?;
+        }
+
+        writer.write_all(b"]")
+    }
+
+    fn begin_object_key<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(if first { b"\n" } else { b",\n" })?;
+        indent(writer, self.current_indent, self.indent)
+    }
+
+    fn begin_object_value<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b": ")
+    }
+
+    fn end_object_value<W>(&mut self, _writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.has_value = true;
+        Ok(())
+    }
+
+    fn begin_array_value<W>(&mut self, writer: &mut W, first: bool) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(if first { b"\n" } else { b",\n" })?;
+        indent(writer, self.current_indent, self.indent)
+    }
+
+    fn end_array_value<W>(&mut self, _writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        self.has_value = true;
+        Ok(())
+    }
+}
+
+fn indent<W>(wr: &mut W, n: usize, s: &[u8]) -> io::Result<()>
+where
+    W: ?Sized + io::Write,
+{
+    for _ in 0..n {
+        wr.write_all(s)?;
+    }
+    Ok(())
+}
+
+pub fn to_writer_pretty<W, T>(writer: W, value: &T) -> Result<()>
 where
+    W: io::Write,
     T: ?Sized + Serialize,
 {
-    let mut writer = Vec::with_capacity(128);
+    let mut ser = Serializer::pretty(writer);
+    value.serialize(&mut ser)
+}
 
-    let mut ser = Serializer::pretty(&mut writer);
-    value.serialize(&mut ser)?;
+pub fn to_vec_pretty<T>(value: &T) -> Result<Vec<u8>>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = Vec::with_capacity(128);
+    to_writer_pretty(&mut writer, value)?;
+    Ok(writer)
+}
 
-    let string = unsafe { String::from_utf8_unchecked(writer) };
+pub fn to_string_pretty<T>(value: &T) -> Result<String>
+where
+    T: ?Sized + Serialize,
+{
+    let vec = to_vec_pretty(value)?;
+    let string = unsafe { String::from_utf8_unchecked(vec) };
 
     Ok(string)
 }
@@ -0,0 +1,371 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::Serializer as _;
+
+use crate::error::{Error, ErrorCode, PathSegment, Result};
+use crate::{
+    check_enum_value, format_enum_value, format_key, Formatter, RenamePolicy, Serializer, Value,
+};
+
+/// Converts JSON to GraphQL input, reading every string as a `String`.
+///
+/// JSON has no enum values, so use [`Value::from_json_with`] to turn some
+/// strings into `Enum`s instead. Integers that do not fit in an `i64` are
+/// rejected rather than rounded to a `Float`.
+impl TryFrom<serde_json::Value> for Value {
+    type Error = Error;
+
+    fn try_from(json: serde_json::Value) -> Result<Self> {
+        Value::from_json_with(json, |_, _| false)
+    }
+}
+
+/// Converts GraphQL input to JSON. `Enum` values and `Variable`s become
+/// strings holding their name, and non-finite floats become `null`.
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Null => serde_json::Value::Null,
+            Value::Int(v) => v.into(),
+            Value::Float(v) => v.into(),
+            Value::String(v) | Value::Enum(v) | Value::Variable(v) => v.into(),
+            Value::Boolean(v) => v.into(),
+            Value::List(values) => {
+                serde_json::Value::Array(values.into_iter().map(Into::into).collect())
+            }
+            Value::Object(fields) => serde_json::Value::Object(
+                fields
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl Value {
+    /// Converts JSON to GraphQL input, calling `resolve` with the path and
+    /// contents of each string. Strings it returns true for become `Enum`
+    /// values, and must then be names other than `true`, `false` or `null`.
+    pub fn from_json_with<R>(json: serde_json::Value, mut resolve: R) -> Result<Value>
+    where
+        R: FnMut(&[PathSegment], &str) -> bool,
+    {
+        from_json(json, &mut Vec::new(), &mut resolve)
+    }
+}
+
+fn from_json<R>(
+    json: serde_json::Value,
+    path: &mut Vec<PathSegment>,
+    resolve: &mut R,
+) -> Result<Value>
+where
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    let value = match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(v) => Value::Boolean(v),
+        serde_json::Value::Number(v) => match (v.as_i64(), v.as_f64()) {
+            (Some(v), _) => Value::Int(v),
+            (None, Some(f)) if v.is_f64() => Value::Float(f),
+            _ => {
+                return Err(Error::from_code(ErrorCode::IntOutOfRange {
+                    value: v.to_string().into(),
+                })
+                .with_path(path))
+            }
+        },
+        serde_json::Value::String(v) if resolve(path, &v) => {
+            check_enum_value(&v).map_err(|err| err.with_path(path))?;
+            Value::Enum(v)
+        }
+        serde_json::Value::String(v) => Value::String(v),
+        serde_json::Value::Array(values) => Value::List(
+            values
+                .into_iter()
+                .enumerate()
+                .map(|(index, value)| {
+                    path.push(PathSegment::Index(index));
+                    let value = from_json(value, path, resolve);
+                    path.pop();
+                    value
+                })
+                .collect::<Result<_>>()?,
+        ),
+        serde_json::Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| {
+                    path.push(PathSegment::Field(Cow::Owned(key.clone())));
+                    let value = from_json(value, path, resolve);
+                    path.pop();
+                    Ok((key, value?))
+                })
+                .collect::<Result<_>>()?,
+        ),
+    };
+
+    Ok(value)
+}
+
+/// Converts JSON text to GraphQL input text, writing every string as a
+/// GraphQL string. See [`from_json_str_with`].
+pub fn from_json_str(json: &str) -> Result<String> {
+    from_json_str_with(json, |_, _| false)
+}
+
+/// Converts JSON text to GraphQL input text in a single pass, without
+/// building an intermediate value.
+///
+/// Object keys are written as names and must be valid GraphQL names. JSON
+/// has no enum values, so `resolve` is called with the path and contents of
+/// each string, and strings it returns true for are written as bare enum
+/// values.
+pub fn from_json_str_with<R>(json: &str, resolve: R) -> Result<String>
+where
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    let mut writer = Vec::with_capacity(json.len());
+    transcode_json(&mut Serializer::new(&mut writer), json, resolve)?;
+    let string = unsafe { String::from_utf8_unchecked(writer) };
+
+    Ok(string)
+}
+
+/// Like [`from_json_str_with`], but writes through `ser`, so its formatter
+/// and policies apply.
+pub fn transcode_json<W, F, R>(ser: &mut Serializer<W, F>, json: &str, resolve: R) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    let mut transcoder = Transcoder {
+        ser,
+        resolve,
+        error: None,
+    };
+    let mut de = serde_json::Deserializer::from_str(json);
+    let result = ValueSeed(&mut transcoder)
+        .deserialize(&mut de)
+        .and_then(|()| de.end());
+
+    result.map_err(|err| match transcoder.error.take() {
+        Some(err) => err,
+        None => Error::from_code(ErrorCode::Json(err)),
+    })
+}
+
+/// Writes JSON values as they are read. Errors raised while writing are kept
+/// aside, as they have to pass through the JSON deserializer as its own
+/// error type.
+struct Transcoder<'a, W, F, R> {
+    ser: &'a mut Serializer<W, F>,
+    resolve: R,
+    error: Option<Error>,
+}
+
+impl<'a, W, F, R> Transcoder<'a, W, F, R>
+where
+    W: io::Write,
+    F: Formatter,
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    fn write<E, G>(&mut self, f: G) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+        G: FnOnce(&mut Serializer<W, F>) -> Result<()>,
+    {
+        f(self.ser).map_err(|err| {
+            self.error = Some(err.with_path(&self.ser.path));
+            E::custom("failed to write GraphQL input")
+        })
+    }
+}
+
+struct ValueSeed<'t, 'a, W, F, R>(&'t mut Transcoder<'a, W, F, R>);
+
+impl<'de, 't, 'a, W, F, R> DeserializeSeed<'de> for ValueSeed<'t, 'a, W, F, R>
+where
+    W: io::Write,
+    F: Formatter,
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<(), D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de, 't, 'a, W, F, R> Visitor<'de> for ValueSeed<'t, 'a, W, F, R>
+where
+    W: io::Write,
+    F: Formatter,
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any JSON value")
+    }
+
+    fn visit_unit<E>(self) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+    {
+        self.0.write(|ser| ser.serialize_unit())
+    }
+
+    fn visit_bool<E>(self, v: bool) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+    {
+        self.0.write(|ser| ser.serialize_bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+    {
+        self.0.write(|ser| ser.serialize_i64(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+    {
+        self.0.write(|ser| ser.serialize_u64(v))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+    {
+        self.0.write(|ser| ser.serialize_f64(v))
+    }
+
+    fn visit_str<E>(self, v: &str) -> std::result::Result<(), E>
+    where
+        E: de::Error,
+    {
+        let t = self.0;
+        if (t.resolve)(&t.ser.path, v) {
+            t.write(|ser| {
+                format_enum_value(&mut ser.writer, &mut ser.formatter, v, RenamePolicy::AsIs)
+            })
+        } else {
+            t.write(|ser| ser.serialize_str(v))
+        }
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> std::result::Result<(), A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let t = self.0;
+        t.write(|ser| {
+            ser.formatter
+                .begin_array(&mut ser.writer)
+                .map_err(Error::io)
+        })?;
+
+        let mut index = 0;
+        loop {
+            t.ser.path.push(PathSegment::Index(index));
+            let element = seq.next_element_seed(ElementSeed {
+                t: &mut *t,
+                first: index == 0,
+            })?;
+            t.ser.path.pop();
+
+            if element.is_none() {
+                break;
+            }
+            index += 1;
+        }
+
+        t.write(|ser| ser.formatter.end_array(&mut ser.writer).map_err(Error::io))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> std::result::Result<(), A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let t = self.0;
+        t.write(|ser| {
+            ser.formatter
+                .begin_object(&mut ser.writer)
+                .map_err(Error::io)
+        })?;
+
+        let mut first = true;
+        while let Some(key) = map.next_key::<String>()? {
+            t.write(|ser| {
+                ser.formatter
+                    .begin_object_key(&mut ser.writer, first)
+                    .map_err(Error::io)?;
+                format_key(&mut ser.writer, &mut ser.formatter, &key, ser.rename_policy)?;
+                ser.formatter
+                    .end_object_key(&mut ser.writer)
+                    .map_err(Error::io)?;
+                ser.formatter
+                    .begin_object_value(&mut ser.writer)
+                    .map_err(Error::io)
+            })?;
+
+            t.ser.path.push(PathSegment::Field(Cow::Owned(key)));
+            map.next_value_seed(ValueSeed(&mut *t))?;
+            t.ser.path.pop();
+
+            t.write(|ser| {
+                ser.formatter
+                    .end_object_value(&mut ser.writer)
+                    .map_err(Error::io)
+            })?;
+            first = false;
+        }
+
+        t.write(|ser| ser.formatter.end_object(&mut ser.writer).map_err(Error::io))
+    }
+}
+
+/// A list element, which is only known to exist once the seed is called.
+struct ElementSeed<'t, 'a, W, F, R> {
+    t: &'t mut Transcoder<'a, W, F, R>,
+    first: bool,
+}
+
+impl<'de, 't, 'a, W, F, R> DeserializeSeed<'de> for ElementSeed<'t, 'a, W, F, R>
+where
+    W: io::Write,
+    F: Formatter,
+    R: FnMut(&[PathSegment], &str) -> bool,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> std::result::Result<(), D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        let first = self.first;
+        self.t.write(|ser| {
+            ser.formatter
+                .begin_array_value(&mut ser.writer, first)
+                .map_err(Error::io)
+        })?;
+        ValueSeed(&mut *self.t).deserialize(deserializer)?;
+        self.t.write(|ser| {
+            ser.formatter
+                .end_array_value(&mut ser.writer)
+                .map_err(Error::io)
+        })
+    }
+}
@@ -1,7 +1,30 @@
+pub mod document;
 pub mod error;
 
+mod arguments;
+mod deserializer;
 mod formatter;
+#[cfg(feature = "json")]
+mod json;
+mod macros;
+mod maybe_undefined;
+mod one_of;
+mod policy;
+mod raw;
 mod serializer;
+mod value;
+mod variable;
 
+pub use arguments::*;
+pub use deserializer::*;
+pub use error::{Category, PathSegment};
 pub use formatter::*;
+#[cfg(feature = "json")]
+pub use json::{from_json_str, from_json_str_with, transcode_json};
+pub use maybe_undefined::MaybeUndefined;
+pub use one_of::OneOf;
+pub use policy::*;
+pub use raw::RawGraphQL;
 pub use serializer::*;
+pub use value::{to_value, Map, Value};
+pub use variable::Variable;
@@ -0,0 +1,97 @@
+/// Builds a [`Value`](crate::Value) from GraphQL input syntax.
+///
+/// ```
+/// # use serde_graphql_input::graphql_input;
+/// let limit = 10;
+/// let value = graphql_input!({
+///     id: $id,
+///     filter: { status: ACTIVE, tags: ["a", "b"] },
+///     limit: (limit),
+/// });
+///
+/// assert_eq!(
+///     r#"{id:$id,filter:{status:ACTIVE,tags:["a","b"]},limit:10}"#,
+///     value.to_string()
+/// );
+/// ```
+///
+/// `null`, `true` and `false` are written as in GraphQL, `$name` is a
+/// variable reference and any other lone identifier is an enum value. Every
+/// other value, such as a literal or a parenthesized Rust expression, is
+/// converted with [`to_value`](crate::to_value), which panics if it fails.
+/// Object keys are names, string literals or parenthesized expressions.
+#[macro_export]
+macro_rules! graphql_input {
+    // The entries of a list, split at each top-level comma. The tokens of the
+    // current element are collected in the parentheses.
+    (@list [$($elems:expr,)*] ()) => {
+        ::std::vec![$($elems,)*]
+    };
+    (@list [$($elems:expr,)*] ($($elem:tt)+)) => {
+        ::std::vec![$($elems,)* $crate::graphql_input!($($elem)+)]
+    };
+    (@list [$($elems:expr,)*] ($($elem:tt)+) , $($rest:tt)*) => {
+        $crate::graphql_input!(@list [$($elems,)* $crate::graphql_input!($($elem)+),] () $($rest)*)
+    };
+    (@list [$($elems:expr,)*] ($($elem:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::graphql_input!(@list [$($elems,)*] ($($elem)* $next) $($rest)*)
+    };
+
+    // The entries of an object, inserted into `$map` one at a time.
+    (@object $map:ident) => {};
+    (@object $map:ident $key:ident : $($rest:tt)*) => {
+        $crate::graphql_input!(@entry $map (::std::string::String::from(::core::stringify!($key))) () $($rest)*)
+    };
+    (@object $map:ident $key:literal : $($rest:tt)*) => {
+        $crate::graphql_input!(@entry $map (::std::string::ToString::to_string(&$key)) () $($rest)*)
+    };
+    (@object $map:ident ($key:expr) : $($rest:tt)*) => {
+        $crate::graphql_input!(@entry $map (::std::string::String::from($key)) () $($rest)*)
+    };
+    (@entry $map:ident ($key:expr) ($($value:tt)+)) => {
+        $map.insert($key, $crate::graphql_input!($($value)+));
+    };
+    (@entry $map:ident ($key:expr) ($($value:tt)+) , $($rest:tt)*) => {
+        $map.insert($key, $crate::graphql_input!($($value)+));
+        $crate::graphql_input!(@object $map $($rest)*);
+    };
+    (@entry $map:ident ($key:expr) ($($value:tt)*) $next:tt $($rest:tt)*) => {
+        $crate::graphql_input!(@entry $map ($key) ($($value)* $next) $($rest)*)
+    };
+
+    (null) => {
+        $crate::Value::Null
+    };
+    (true) => {
+        $crate::Value::Boolean(true)
+    };
+    (false) => {
+        $crate::Value::Boolean(false)
+    };
+    ([ $($tt:tt)* ]) => {
+        $crate::Value::List($crate::graphql_input!(@list [] () $($tt)*))
+    };
+    ({ $($tt:tt)* }) => {
+        $crate::Value::Object({
+            #[allow(unused_mut)]
+            let mut map = $crate::Map::new();
+            $crate::graphql_input!(@object map $($tt)*);
+            map
+        })
+    };
+    // A `$` cannot be matched directly, so any token followed by a name is
+    // taken here and checked to be a `$`.
+    ($dollar:tt $name:ident) => {{
+        const _: () = ::core::assert!(
+            ::core::matches!(::core::stringify!($dollar).as_bytes(), b"$"),
+            "expected a `$name` variable reference, wrap other expressions in parentheses"
+        );
+        $crate::Value::Variable(::std::string::String::from(::core::stringify!($name)))
+    }};
+    ($name:ident) => {
+        $crate::Value::Enum(::std::string::String::from(::core::stringify!($name)))
+    };
+    ($other:expr) => {
+        $crate::to_value(&$other).unwrap()
+    };
+}
@@ -0,0 +1,340 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Impossible, Serialize, Serializer};
+
+pub(crate) const TOKEN: &str = "$serde_graphql_input::private::Undefined";
+
+/// A value that can be left out, set to `null` or set to a value.
+///
+/// GraphQL treats an omitted field differently from an explicit `null`,
+/// which typically clears it. Object fields holding `Undefined` are dropped
+/// from the output altogether, and `Null` is written as `null`. Anywhere
+/// else, such as in a list, `Undefined` is written as `null` too.
+///
+/// When deserializing, a missing field is only read as `Undefined` if the
+/// field has `#[serde(default)]`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MaybeUndefined<T> {
+    #[default]
+    Undefined,
+    Null,
+    Value(T),
+}
+
+impl<T> MaybeUndefined<T> {
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, MaybeUndefined::Undefined)
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, MaybeUndefined::Null)
+    }
+
+    pub fn is_value(&self) -> bool {
+        matches!(self, MaybeUndefined::Value(_))
+    }
+
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            MaybeUndefined::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// `None` if undefined, `Some(None)` if null and `Some(Some(value))`
+    /// otherwise.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            MaybeUndefined::Undefined => None,
+            MaybeUndefined::Null => Some(None),
+            MaybeUndefined::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T> From<Option<T>> for MaybeUndefined<T> {
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => MaybeUndefined::Value(value),
+            None => MaybeUndefined::Null,
+        }
+    }
+}
+
+impl<T> Serialize for MaybeUndefined<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            MaybeUndefined::Undefined => serializer.serialize_unit_struct(TOKEN),
+            MaybeUndefined::Null => serializer.serialize_none(),
+            MaybeUndefined::Value(value) => serializer.serialize_some(value),
+        }
+    }
+}
+
+impl<'de, T> Deserialize<'de> for MaybeUndefined<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct MaybeUndefinedVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for MaybeUndefinedVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = MaybeUndefined<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("an optional value")
+            }
+
+            fn visit_none<E>(self) -> std::result::Result<Self::Value, E> {
+                Ok(MaybeUndefined::Null)
+            }
+
+            fn visit_unit<E>(self) -> std::result::Result<Self::Value, E> {
+                Ok(MaybeUndefined::Null)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> std::result::Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(MaybeUndefined::Value)
+            }
+        }
+
+        deserializer.deserialize_option(MaybeUndefinedVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Whether `value` serializes as `MaybeUndefined::Undefined`, looking through
+/// newtype structs.
+pub(crate) fn is_undefined<T>(value: &T) -> bool
+where
+    T: Serialize + ?Sized,
+{
+    value.serialize(UndefinedProbe).unwrap_or(false)
+}
+
+/// Answers whether a value is undefined, stopping at the first call.
+/// Compound values are never undefined and are cut short with [`Defined`].
+struct UndefinedProbe;
+
+/// Raised by [`UndefinedProbe`] to stop at a compound value, without
+/// allocating.
+#[derive(Debug)]
+struct Defined;
+
+impl fmt::Display for Defined {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("value is defined")
+    }
+}
+
+impl std::error::Error for Defined {}
+
+impl serde::ser::Error for Defined {
+    fn custom<T>(_msg: T) -> Self
+    where
+        T: fmt::Display,
+    {
+        Defined
+    }
+}
+
+impl Serializer for UndefinedProbe {
+    type Ok = bool;
+    type Error = Defined;
+
+    type SerializeSeq = Impossible<bool, Defined>;
+    type SerializeTuple = Impossible<bool, Defined>;
+    type SerializeTupleStruct = Impossible<bool, Defined>;
+    type SerializeTupleVariant = Impossible<bool, Defined>;
+    type SerializeMap = Impossible<bool, Defined>;
+    type SerializeStruct = Impossible<bool, Defined>;
+    type SerializeStructVariant = Impossible<bool, Defined>;
+
+    fn serialize_bool(self, _v: bool) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_i8(self, _v: i8) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_i16(self, _v: i16) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_i32(self, _v: i32) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_i64(self, _v: i64) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_i128(self, _v: i128) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_u8(self, _v: u8) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_u16(self, _v: u16) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_u32(self, _v: u32) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_u64(self, _v: u64) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_u128(self, _v: u128) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_f32(self, _v: f32) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_f64(self, _v: f64) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_char(self, _v: char) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_str(self, _v: &str) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_none(self) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> std::result::Result<bool, Defined>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Ok(false)
+    }
+
+    fn serialize_unit(self) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_unit_struct(self, name: &'static str) -> std::result::Result<bool, Defined> {
+        Ok(name == TOKEN)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> std::result::Result<bool, Defined> {
+        Ok(false)
+    }
+
+    fn serialize_newtype_struct<T>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> std::result::Result<bool, Defined>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> std::result::Result<bool, Defined>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Ok(false)
+    }
+
+    fn serialize_seq(
+        self,
+        _len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeSeq, Defined> {
+        Err(Defined)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> std::result::Result<Self::SerializeTuple, Defined> {
+        Err(Defined)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleStruct, Defined> {
+        Err(Defined)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeTupleVariant, Defined> {
+        Err(Defined)
+    }
+
+    fn serialize_map(
+        self,
+        _len: Option<usize>,
+    ) -> std::result::Result<Self::SerializeMap, Defined> {
+        Err(Defined)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStruct, Defined> {
+        Err(Defined)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> std::result::Result<Self::SerializeStructVariant, Defined> {
+        Err(Defined)
+    }
+}
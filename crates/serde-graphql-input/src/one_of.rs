@@ -0,0 +1,281 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::io;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Impossible, Serialize, Serializer};
+
+use crate::error::{Error, ErrorCode, Kind, Result};
+use crate::{Formatter, RenamePolicy};
+
+pub(crate) const TOKEN: &str = "$serde_graphql_input::private::OneOf";
+
+/// Marks an enum as a GraphQL `@oneOf` input object.
+///
+/// Every variant of the wrapped enum must be a newtype variant. `Lookup::ById(1)`
+/// is written as `{byId:1}`, with the variant name converted to camelCase.
+/// Unit, tuple and struct variants are rejected with an error.
+///
+/// Other serializers see a plain newtype struct, so the wrapper is transparent
+/// when the same type is serialized as JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct OneOf<T>(pub T);
+
+impl<T> OneOf<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for OneOf<T> {
+    fn from(value: T) -> Self {
+        OneOf(value)
+    }
+}
+
+impl<T> Serialize for OneOf<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(TOKEN, &self.0)
+    }
+}
+
+impl<'de, T> Deserialize<'de> for OneOf<T>
+where
+    T: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct OneOfVisitor<T>(std::marker::PhantomData<T>);
+
+        impl<'de, T> Visitor<'de> for OneOfVisitor<T>
+        where
+            T: Deserialize<'de>,
+        {
+            type Value = OneOf<T>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a oneOf input object")
+            }
+
+            fn visit_newtype_struct<D>(
+                self,
+                deserializer: D,
+            ) -> std::result::Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                T::deserialize(deserializer).map(OneOf)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(TOKEN, OneOfVisitor(std::marker::PhantomData))
+    }
+}
+
+/// Converts a variant name to the field name used in the `@oneOf` object,
+/// camelCasing it as [`RenamePolicy::CamelCase`] does.
+pub(crate) fn field_name(variant: &str) -> String {
+    RenamePolicy::CamelCase.apply(variant).into_owned()
+}
+
+pub(crate) struct OneOfSerializer<'a, W: 'a, F: 'a> {
+    pub(crate) ser: &'a mut crate::Serializer<W, F>,
+}
+
+impl<'a, W, F> OneOfSerializer<'a, W, F> {
+    fn expected_enum(kind: Kind) -> Error {
+        Error::from_code(ErrorCode::OneOfExpectedEnum { kind })
+    }
+
+    fn variant_fields(variant: &str, fields: usize) -> Error {
+        Error::from_code(ErrorCode::OneOfVariantFields {
+            variant: variant.into(),
+            fields,
+        })
+    }
+}
+
+impl<'a, W, F> Serializer for OneOfSerializer<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    type Ok = ();
+    type Error = Error;
+
+    type SerializeSeq = Impossible<(), Error>;
+    type SerializeTuple = Impossible<(), Error>;
+    type SerializeTupleStruct = Impossible<(), Error>;
+    type SerializeTupleVariant = Impossible<(), Error>;
+    type SerializeMap = Impossible<(), Error>;
+    type SerializeStruct = Impossible<(), Error>;
+    type SerializeStructVariant = Impossible<(), Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<()> {
+        Err(Self::expected_enum(Kind::Bool))
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<()> {
+        Err(Self::expected_enum(Kind::I128))
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<()> {
+        Err(Self::expected_enum(Kind::Integer))
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<()> {
+        Err(Self::expected_enum(Kind::U128))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<()> {
+        Err(Self::expected_enum(Kind::Float))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<()> {
+        Err(Self::expected_enum(Kind::Float))
+    }
+
+    fn serialize_char(self, _v: char) -> Result<()> {
+        Err(Self::expected_enum(Kind::Char))
+    }
+
+    fn serialize_str(self, _v: &str) -> Result<()> {
+        Err(Self::expected_enum(Kind::String))
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<()> {
+        Err(Self::expected_enum(Kind::Bytes))
+    }
+
+    fn serialize_none(self) -> Result<()> {
+        Err(Self::expected_enum(Kind::None))
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Err(Self::expected_enum(Kind::Some))
+    }
+
+    fn serialize_unit(self) -> Result<()> {
+        Err(Self::expected_enum(Kind::Unit))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
+        Err(Self::expected_enum(Kind::UnitStruct))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<()> {
+        Err(Self::variant_fields(variant, 0))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        self.ser
+            .serialize_variant_value(Cow::Owned(field_name(variant)), value)
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(Self::expected_enum(Kind::Seq))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(Self::expected_enum(Kind::Tuple))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(Self::expected_enum(Kind::TupleStruct))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(Self::variant_fields(variant, len))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(Self::expected_enum(Kind::Map))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(Self::expected_enum(Kind::Struct))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(Self::variant_fields(variant, len))
+    }
+}
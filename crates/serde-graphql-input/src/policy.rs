@@ -0,0 +1,178 @@
+use std::borrow::Cow;
+
+/// How the `Serializer` writes byte arrays, which have no GraphQL counterpart.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BytesPolicy {
+    /// A string in padded standard base64, e.g. `"aGk="`.
+    #[default]
+    Base64,
+    /// A string in unpadded URL-safe base64, e.g. `"aGk"`.
+    Base64UrlSafe,
+    /// A string of lowercase hex digits, e.g. `"6869"`.
+    Hex,
+    /// A list of `Int`, e.g. `[104,105]`.
+    List,
+    /// Reject byte arrays with an error.
+    Error,
+}
+
+/// How the `Serializer` writes `NaN` and infinite floats, which GraphQL's
+/// `Float` cannot represent.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloatPolicy {
+    /// Reject non-finite floats with an error.
+    #[default]
+    Error,
+    /// Write `null`.
+    Null,
+    /// A string, one of `"NaN"`, `"Infinity"` or `"-Infinity"`.
+    String,
+}
+
+/// How the `Serializer` writes integers outside the signed 32-bit range of
+/// GraphQL's `Int`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IntPolicy {
+    /// Reject out of range integers with an error.
+    #[default]
+    Error,
+    /// Write the integer as is, for servers with a wider `Int` or custom
+    /// integer scalars.
+    PassThrough,
+    /// A string of decimal digits, e.g. `"9007199254740993"`, as expected by
+    /// `BigInt` or `Long` custom scalars.
+    String,
+}
+
+/// How the `Serializer` writes strings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StringPolicy {
+    /// Always a quoted string, e.g. `"one\ntwo"`.
+    #[default]
+    Quoted,
+    /// A `"""` block string for strings with a line break that are at least
+    /// `threshold` bytes long. Strings that would read back differently as a
+    /// block string, such as ones with leading blank lines, stay quoted.
+    Block { threshold: usize },
+}
+
+/// How the `Serializer` writes non-ASCII characters in strings, for
+/// transports that do not preserve UTF-8.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum UnicodePolicy {
+    /// As is, encoded as UTF-8.
+    #[default]
+    Raw,
+    /// `\uXXXX` escapes of the UTF-16 code units, e.g. `"\u00e6\ud83d\ude00"`
+    /// for `"æ😀"`. Valid in every edition of the spec, though editions up to
+    /// October 2021 leave it to the server to join surrogate pairs.
+    EscapeUtf16,
+    /// `\u{X}` escapes of the code point, e.g. `"\u{e6}\u{1f600}"` for
+    /// `"æ😀"`. Only in the working draft that followed the October 2021
+    /// edition, which has just the four-digit `\uXXXX` form, so it requires a
+    /// server that implements the draft.
+    EscapeCodePoint,
+}
+
+/// How the `Serializer` renames object keys and enum values, for types that
+/// cannot be annotated with `#[serde(rename_all = "...")]`.
+///
+/// Names are split into words at underscores and at changes of case, so
+/// `user_id`, `userId` and `UserId` all become `userId` under `CamelCase`.
+#[derive(Clone, Copy, Debug, Default)]
+pub enum RenamePolicy {
+    /// Names are written as they are.
+    #[default]
+    AsIs,
+    /// `userId`
+    CamelCase,
+    /// `UserId`
+    PascalCase,
+    /// `USER_ID`
+    ScreamingSnakeCase,
+    /// Names are passed through the function.
+    Custom(fn(&str) -> Cow<'_, str>),
+}
+
+impl RenamePolicy {
+    /// Renames `name` according to the policy.
+    pub fn apply<'a>(&self, name: &'a str) -> Cow<'a, str> {
+        let renamed = match self {
+            RenamePolicy::AsIs => return Cow::Borrowed(name),
+            RenamePolicy::Custom(rename) => return rename(name),
+            RenamePolicy::CamelCase => join_words(name, |out, i, word| match i {
+                0 => out.extend(word.chars().flat_map(char::to_lowercase)),
+                _ => push_capitalized(out, word),
+            }),
+            RenamePolicy::PascalCase => join_words(name, |out, _, word| {
+                push_capitalized(out, word);
+            }),
+            RenamePolicy::ScreamingSnakeCase => join_words(name, |out, i, word| {
+                if i > 0 {
+                    out.push('_');
+                }
+                out.extend(word.chars().flat_map(char::to_uppercase));
+            }),
+        };
+
+        if renamed == name {
+            Cow::Borrowed(name)
+        } else {
+            Cow::Owned(renamed)
+        }
+    }
+}
+
+/// Writes each word of `name` with `push`, keeping leading underscores as in
+/// `__typename`.
+fn join_words<F>(name: &str, mut push: F) -> String
+where
+    F: FnMut(&mut String, usize, &str),
+{
+    let body = name.trim_start_matches('_');
+
+    let mut out = String::with_capacity(name.len() + 4);
+    out.push_str(&name[..name.len() - body.len()]);
+    for (i, word) in words(body).enumerate() {
+        push(&mut out, i, word);
+    }
+    out
+}
+
+fn push_capitalized(out: &mut String, word: &str) {
+    let mut chars = word.chars();
+    if let Some(first) = chars.next() {
+        out.extend(first.to_uppercase());
+        out.extend(chars.flat_map(char::to_lowercase));
+    }
+}
+
+/// Splits a name into words at underscores, before an uppercase letter that
+/// follows a lowercase letter or digit, and before the last uppercase letter
+/// of an acronym followed by a lowercase letter (`HTTPServer` is `HTTP`,
+/// `Server`).
+fn words(name: &str) -> impl Iterator<Item = &str> {
+    name.split('_')
+        .flat_map(|part| {
+            let chars: Vec<(usize, char)> = part.char_indices().collect();
+            let mut bounds = vec![0];
+            for i in 1..chars.len() {
+                let (index, ch) = chars[i];
+                let prev = chars[i - 1].1;
+                let next = chars.get(i + 1).map(|&(_, ch)| ch);
+                let starts_word = ch.is_uppercase()
+                    && (prev.is_lowercase()
+                        || prev.is_numeric()
+                        || (prev.is_uppercase() && next.is_some_and(char::is_lowercase)));
+                if starts_word {
+                    bounds.push(index);
+                }
+            }
+            bounds.push(part.len());
+            bounds
+                .windows(2)
+                .map(|w| &part[w[0]..w[1]])
+                .collect::<Vec<_>>()
+        })
+        .filter(|word| !word.is_empty())
+}
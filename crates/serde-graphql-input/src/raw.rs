@@ -0,0 +1,97 @@
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::error::Result;
+
+pub(crate) const TOKEN: &str = "$serde_graphql_input::private::RawGraphQL";
+
+/// A pre-rendered fragment of GraphQL input text that is written verbatim.
+///
+/// Use [`RawGraphQL::from_string`] to check that the fragment is a single
+/// well-formed input value before embedding it. Deserializing, from any format,
+/// checks the text the same way. Other serializers see a plain newtype struct
+/// around the text.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RawGraphQL {
+    text: String,
+}
+
+impl RawGraphQL {
+    /// Parses `text` as a GraphQL input value and wraps it if it is valid.
+    ///
+    /// The value may only be surrounded by whitespace. Commas and comments
+    /// are rejected, as a comment would swallow the rest of the line once
+    /// the text is embedded.
+    pub fn from_string(text: String) -> Result<Self> {
+        crate::Deserializer::from_str(&text).parse_embeddable_value()?;
+
+        Ok(RawGraphQL { text })
+    }
+
+    /// Wraps `text` without checking it. The caller is responsible for it
+    /// being a valid GraphQL input value.
+    pub fn from_string_unchecked(text: String) -> Self {
+        RawGraphQL { text }
+    }
+
+    pub fn get(&self) -> &str {
+        &self.text
+    }
+
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl fmt::Debug for RawGraphQL {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawGraphQL").field(&self.text).finish()
+    }
+}
+
+impl fmt::Display for RawGraphQL {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+impl Serialize for RawGraphQL {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(TOKEN, &self.text)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawGraphQL {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct RawVisitor;
+
+        impl<'de> Visitor<'de> for RawVisitor {
+            type Value = RawGraphQL;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a raw GraphQL input value")
+            }
+
+            fn visit_newtype_struct<D>(
+                self,
+                deserializer: D,
+            ) -> std::result::Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                let text = String::deserialize(deserializer)?;
+                RawGraphQL::from_string(text).map_err(de::Error::custom)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(TOKEN, RawVisitor)
+    }
+}
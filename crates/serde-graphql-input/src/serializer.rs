@@ -1,16 +1,48 @@
+use std::borrow::Cow;
 use std::io;
 
+use base64::prelude::{Engine as _, BASE64_STANDARD, BASE64_URL_SAFE_NO_PAD};
 use serde::ser::Impossible;
 use serde::Serialize;
 
-use crate::error::{self, Result};
-use crate::{error::Error, CompactFormatter, Formatter, PrettyFormatter};
-use crate::{format_escaped_str, format_key};
+use crate::error::{self, ErrorCode, Kind, PathSegment, Result};
+use crate::maybe_undefined;
+use crate::one_of::{self, OneOfSerializer};
+use crate::raw;
+use crate::value;
+use crate::variable;
+use crate::{
+    error::Error, BytesPolicy, CompactFormatter, FloatPolicy, Formatter, IntPolicy,
+    PrettyFormatter, RenamePolicy, StringPolicy, UnicodePolicy,
+};
+use crate::{
+    format_block_str, format_enum_value, format_escaped_str, format_key, is_block_string_safe,
+    is_valid_name,
+};
+
+static HEX_DIGITS: [u8; 16] = *b"0123456789abcdef";
 
 #[allow(dead_code)]
 pub struct Serializer<W, F = CompactFormatter> {
     pub(crate) writer: W,
     pub(crate) formatter: F,
+    pub(crate) bytes_policy: BytesPolicy,
+    pub(crate) float_policy: FloatPolicy,
+    pub(crate) int_policy: IntPolicy,
+    pub(crate) string_policy: StringPolicy,
+    pub(crate) unicode_policy: UnicodePolicy,
+    pub(crate) rename_policy: RenamePolicy,
+    pub(crate) enum_value_policy: Option<RenamePolicy>,
+    pub(crate) path: Vec<PathSegment>,
+}
+
+impl<W> Serializer<W>
+where
+    W: io::Write,
+{
+    pub fn new(writer: W) -> Self {
+        Serializer::with_formatter(writer, CompactFormatter)
+    }
 }
 
 impl<'a, W> Serializer<W, PrettyFormatter<'a>>
@@ -28,7 +60,150 @@ where
     F: Formatter,
 {
     pub fn with_formatter(writer: W, formatter: F) -> Self {
-        Serializer { writer, formatter }
+        Serializer {
+            writer,
+            formatter,
+            bytes_policy: BytesPolicy::default(),
+            float_policy: FloatPolicy::default(),
+            int_policy: IntPolicy::default(),
+            string_policy: StringPolicy::default(),
+            unicode_policy: UnicodePolicy::default(),
+            rename_policy: RenamePolicy::default(),
+            enum_value_policy: None,
+            path: Vec::new(),
+        }
+    }
+
+    pub fn with_bytes_policy(mut self, policy: BytesPolicy) -> Self {
+        self.bytes_policy = policy;
+        self
+    }
+
+    pub fn with_float_policy(mut self, policy: FloatPolicy) -> Self {
+        self.float_policy = policy;
+        self
+    }
+
+    pub fn with_int_policy(mut self, policy: IntPolicy) -> Self {
+        self.int_policy = policy;
+        self
+    }
+
+    pub fn with_string_policy(mut self, policy: StringPolicy) -> Self {
+        self.string_policy = policy;
+        self
+    }
+
+    pub fn with_unicode_policy(mut self, policy: UnicodePolicy) -> Self {
+        self.unicode_policy = policy;
+        self
+    }
+
+    pub fn with_rename_policy(mut self, policy: RenamePolicy) -> Self {
+        self.rename_policy = policy;
+        self
+    }
+
+    /// Renames enum values, i.e. unit variants, with `policy` instead of the
+    /// rename policy. `RenamePolicy::ScreamingSnakeCase` follows the GraphQL
+    /// convention of `ENUM_VALUE`.
+    pub fn with_enum_value_policy(mut self, policy: RenamePolicy) -> Self {
+        self.enum_value_policy = Some(policy);
+        self
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Writes an integer outside GraphQL's `Int` range according to the int
+    /// policy.
+    fn serialize_out_of_range<I>(&mut self, value: I) -> Result<()>
+    where
+        I: itoa::Integer,
+    {
+        let mut buffer = itoa::Buffer::new();
+        let value = buffer.format(value);
+        match self.int_policy {
+            IntPolicy::String => format_escaped_str(
+                &mut self.writer,
+                &mut self.formatter,
+                value,
+                self.unicode_policy,
+            )
+            .map_err(Error::io),
+            _ => Err(Error::from_code(ErrorCode::IntOutOfRange {
+                value: value.into(),
+            })),
+        }
+    }
+
+    /// Writes `NaN` or an infinity according to the float policy.
+    fn serialize_non_finite(&mut self, value: f64) -> Result<()> {
+        match self.float_policy {
+            FloatPolicy::Error => Err(Error::from_code(ErrorCode::FloatNotFinite { value })),
+            FloatPolicy::Null => self
+                .formatter
+                .write_null(&mut self.writer)
+                .map_err(Error::io),
+            FloatPolicy::String => {
+                let s = if value.is_nan() {
+                    "NaN"
+                } else if value.is_sign_positive() {
+                    "Infinity"
+                } else {
+                    "-Infinity"
+                };
+                format_escaped_str(
+                    &mut self.writer,
+                    &mut self.formatter,
+                    s,
+                    self.unicode_policy,
+                )
+                .map_err(Error::io)
+            }
+        }
+    }
+
+    /// Writes an externally tagged `{variant:value}` object.
+    pub(crate) fn serialize_variant_value<T>(
+        &mut self,
+        variant: Cow<'static, str>,
+        value: &T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        self.formatter
+            .begin_object(&mut self.writer)
+            .map_err(Error::io)?;
+        self.formatter
+            .begin_object_key(&mut self.writer, true)
+            .map_err(Error::io)?;
+        format_key(
+            &mut self.writer,
+            &mut self.formatter,
+            &variant,
+            self.rename_policy,
+        )?;
+        self.formatter
+            .end_object_key(&mut self.writer)
+            .map_err(Error::io)?;
+        self.formatter
+            .begin_object_value(&mut self.writer)
+            .map_err(Error::io)?;
+        self.path.push(PathSegment::Variant(variant));
+        value
+            .serialize(&mut *self)
+            .map_err(|err| err.with_path(&self.path))?;
+        self.path.pop();
+        self.formatter
+            .end_object_value(&mut self.writer)
+            .map_err(Error::io)?;
+        self.formatter
+            .end_object(&mut self.writer)
+            .map_err(Error::io)
     }
 }
 
@@ -74,11 +249,23 @@ where
     }
 
     fn serialize_i64(self, v: i64) -> Result<()> {
+        if i32::try_from(v).is_err() && self.int_policy != IntPolicy::PassThrough {
+            return self.serialize_out_of_range(v);
+        }
         self.formatter
             .write_i64(&mut self.writer, v)
             .map_err(Error::io)
     }
 
+    fn serialize_i128(self, v: i128) -> Result<()> {
+        if i32::try_from(v).is_err() && self.int_policy != IntPolicy::PassThrough {
+            return self.serialize_out_of_range(v);
+        }
+        self.formatter
+            .write_i128(&mut self.writer, v)
+            .map_err(Error::io)
+    }
+
     fn serialize_u8(self, v: u8) -> Result<()> {
         self.formatter
             .write_u8(&mut self.writer, v)
@@ -92,41 +279,104 @@ where
     }
 
     fn serialize_u32(self, v: u32) -> Result<()> {
+        if i32::try_from(v).is_err() && self.int_policy != IntPolicy::PassThrough {
+            return self.serialize_out_of_range(v);
+        }
         self.formatter
             .write_u32(&mut self.writer, v)
             .map_err(Error::io)
     }
 
     fn serialize_u64(self, v: u64) -> Result<()> {
+        if i32::try_from(v).is_err() && self.int_policy != IntPolicy::PassThrough {
+            return self.serialize_out_of_range(v);
+        }
         self.formatter
             .write_u64(&mut self.writer, v)
             .map_err(Error::io)
     }
 
+    fn serialize_u128(self, v: u128) -> Result<()> {
+        if i32::try_from(v).is_err() && self.int_policy != IntPolicy::PassThrough {
+            return self.serialize_out_of_range(v);
+        }
+        self.formatter
+            .write_u128(&mut self.writer, v)
+            .map_err(Error::io)
+    }
+
     fn serialize_f32(self, v: f32) -> Result<()> {
+        if !v.is_finite() {
+            return self.serialize_non_finite(v.into());
+        }
         self.formatter
             .write_f32(&mut self.writer, v)
             .map_err(Error::io)
     }
 
     fn serialize_f64(self, v: f64) -> Result<()> {
+        if !v.is_finite() {
+            return self.serialize_non_finite(v);
+        }
         self.formatter
             .write_f64(&mut self.writer, v)
             .map_err(Error::io)
     }
 
     fn serialize_char(self, v: char) -> Result<()> {
-        self.formatter
-            .write_char(&mut self.writer, v)
-            .map_err(Error::io)
+        let mut buf = [0; 4];
+        self.serialize_str(v.encode_utf8(&mut buf))
     }
 
     fn serialize_str(self, v: &str) -> Result<()> {
-        format_escaped_str(&mut self.writer, &mut self.formatter, v).map_err(Error::io)
+        if let StringPolicy::Block { threshold } = self.string_policy {
+            if v.len() >= threshold
+                && v.contains('\n')
+                && (v.is_ascii() || self.unicode_policy == UnicodePolicy::Raw)
+                && is_block_string_safe(v)
+            {
+                return format_block_str(&mut self.writer, &mut self.formatter, v)
+                    .map_err(Error::io);
+            }
+        }
+        format_escaped_str(
+            &mut self.writer,
+            &mut self.formatter,
+            v,
+            self.unicode_policy,
+        )
+        .map_err(Error::io)
     }
 
     fn serialize_bytes(self, v: &[u8]) -> Result<()> {
-        todo!()
+        let encoded = match self.bytes_policy {
+            BytesPolicy::Base64 => BASE64_STANDARD.encode(v),
+            BytesPolicy::Base64UrlSafe => BASE64_URL_SAFE_NO_PAD.encode(v),
+            BytesPolicy::Hex => {
+                let mut encoded = String::with_capacity(v.len() * 2);
+                for byte in v {
+                    encoded.push(HEX_DIGITS[(byte >> 4) as usize] as char);
+                    encoded.push(HEX_DIGITS[(byte & 0xF) as usize] as char);
+                }
+                encoded
+            }
+            BytesPolicy::List => {
+                let mut seq = self.serialize_seq(Some(v.len()))?;
+                for byte in v {
+                    serde::ser::SerializeSeq::serialize_element(&mut seq, byte)?;
+                }
+                return serde::ser::SerializeSeq::end(seq);
+            }
+            BytesPolicy::Error => return Err(Error::from_code(ErrorCode::BytesNotAllowed)),
+        };
+
+        format_escaped_str(
+            &mut self.writer,
+            &mut self.formatter,
+            &encoded,
+            self.unicode_policy,
+        )
+        .map_err(Error::io)
     }
 
     fn serialize_none(self) -> Result<()> {
@@ -159,9 +409,8 @@ where
         variant_index: u32,
         variant: &'static str,
     ) -> Result<()> {
-        self.formatter
-            .write_string(&mut self.writer, variant)
-            .map_err(Error::io)
+        let policy = self.enum_value_policy.unwrap_or(self.rename_policy);
+        format_enum_value(&mut self.writer, &mut self.formatter, variant, policy)
     }
 
     fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
@@ -169,6 +418,45 @@ where
         T: Serialize,
         T: ?Sized,
     {
+        if name == one_of::TOKEN {
+            return value.serialize(OneOfSerializer { ser: self });
+        }
+
+        if name == raw::TOKEN {
+            let raw = value.serialize(StringCollector {
+                context: "raw GraphQL",
+            })?;
+            return self
+                .formatter
+                .write_string(&mut self.writer, &raw)
+                .map_err(Error::io);
+        }
+
+        if name == value::ENUM_TOKEN {
+            let name = value.serialize(StringCollector {
+                context: "enum value",
+            })?;
+            return format_enum_value(
+                &mut self.writer,
+                &mut self.formatter,
+                &name,
+                RenamePolicy::AsIs,
+            );
+        }
+
+        if name == variable::TOKEN {
+            let name = value.serialize(StringCollector {
+                context: "variable name",
+            })?;
+            if !is_valid_name(&name) {
+                return Err(Error::invalid_name(&name));
+            }
+            return self
+                .formatter
+                .write_variable(&mut self.writer, &name)
+                .map_err(Error::io);
+        }
+
         value.serialize(self)
     }
 
@@ -183,26 +471,7 @@ where
         T: Serialize,
         T: ?Sized,
     {
-        self.formatter
-            .begin_object(&mut self.writer)
-            .map_err(Error::io)?;
-        self.formatter
-            .begin_object_key(&mut self.writer, true)
-            .map_err(Error::io)?;
-        self.serialize_str(variant)?;
-        self.formatter
-            .end_object_key(&mut self.writer)
-            .map_err(Error::io)?;
-        self.formatter
-            .begin_object_value(&mut self.writer)
-            .map_err(Error::io)?;
-        value.serialize(&mut *self)?;
-        self.formatter
-            .end_object_value(&mut self.writer)
-            .map_err(Error::io)?;
-        self.formatter
-            .end_object(&mut self.writer)
-            .map_err(Error::io)
+        self.serialize_variant_value(Cow::Borrowed(variant), value)
     }
 
     fn serialize_seq(
@@ -258,13 +527,19 @@ where
         self.formatter
             .begin_object_key(&mut self.writer, true)
             .map_err(Error::io)?;
-        self.serialize_str(variant)?;
+        format_key(
+            &mut self.writer,
+            &mut self.formatter,
+            variant,
+            self.rename_policy,
+        )?;
         self.formatter
             .end_object_key(&mut self.writer)
             .map_err(Error::io)?;
         self.formatter
             .begin_object_value(&mut self.writer)
             .map_err(Error::io)?;
+        self.path.push(PathSegment::Variant(Cow::Borrowed(variant)));
         self.serialize_seq(Some(len))
     }
 
@@ -312,128 +587,199 @@ where
         self.formatter
             .begin_object_key(&mut self.writer, true)
             .map_err(Error::io)?;
-        self.serialize_str(variant)?;
+        format_key(
+            &mut self.writer,
+            &mut self.formatter,
+            variant,
+            self.rename_policy,
+        )?;
         self.formatter
             .end_object_key(&mut self.writer)
             .map_err(Error::io)?;
         self.formatter
             .begin_object_value(&mut self.writer)
             .map_err(Error::io)?;
+        self.path.push(PathSegment::Variant(Cow::Borrowed(variant)));
         self.serialize_map(Some(len))
     }
 }
 
-struct MapKeySerializer<'a, W: 'a, F: 'a> {
-    ser: &'a mut Serializer<W, F>,
+pub fn to_writer_with_formatter<W, F, T>(writer: W, formatter: F, value: &T) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+    T: ?Sized + Serialize,
+{
+    let mut ser = Serializer::with_formatter(writer, formatter);
+    value.serialize(&mut ser)
 }
 
-impl<'a, W, F> serde::ser::Serializer for MapKeySerializer<'a, W, F>
+pub fn to_vec_with_formatter<F, T>(formatter: F, value: &T) -> Result<Vec<u8>>
 where
-    W: io::Write,
     F: Formatter,
+    T: ?Sized + Serialize,
 {
-    type Ok = ();
+    let mut writer = Vec::with_capacity(128);
+    to_writer_with_formatter(&mut writer, formatter, value)?;
+    Ok(writer)
+}
+
+/// Serializes `value` to a string with `formatter`. As the formatter may be
+/// any implementation, its output is checked to be UTF-8, failing with an
+/// I/O error of kind `InvalidData` otherwise.
+pub fn to_string_with_formatter<F, T>(formatter: F, value: &T) -> Result<String>
+where
+    F: Formatter,
+    T: ?Sized + Serialize,
+{
+    let vec = to_vec_with_formatter(formatter, value)?;
+    String::from_utf8(vec).map_err(|err| Error::io(io::Error::new(io::ErrorKind::InvalidData, err)))
+}
+
+/// Converts a map key into the name it is written as.
+pub(crate) struct MapKeySerializer;
+
+impl MapKeySerializer {
+    fn serialize_integer<I>(self, value: I) -> Result<String>
+    where
+        I: itoa::Integer,
+    {
+        let mut buffer = itoa::Buffer::new();
+        Ok(buffer.format(value).to_owned())
+    }
+}
+
+impl serde::ser::Serializer for MapKeySerializer {
+    type Ok = String;
     type Error = Error;
 
-    type SerializeSeq = Impossible<(), Error>;
-    type SerializeTuple = Impossible<(), Error>;
-    type SerializeTupleStruct = Impossible<(), Error>;
-    type SerializeTupleVariant = Impossible<(), Error>;
-    type SerializeMap = Impossible<(), Error>;
-    type SerializeStruct = Impossible<(), Error>;
-    type SerializeStructVariant = Impossible<(), Error>;
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
 
-    fn serialize_bool(self, _v: bool) -> Result<()> {
-        todo!()
+    fn serialize_bool(self, v: bool) -> Result<String> {
+        Ok(if v { "true" } else { "false" }.to_owned())
     }
 
-    fn serialize_i8(self, _v: i8) -> Result<()> {
-        todo!()
+    fn serialize_i8(self, v: i8) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_i16(self, _v: i16) -> Result<()> {
-        todo!()
+    fn serialize_i16(self, v: i16) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_i32(self, _v: i32) -> Result<()> {
-        todo!()
+    fn serialize_i32(self, v: i32) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_i64(self, _v: i64) -> Result<()> {
-        todo!()
+    fn serialize_i64(self, v: i64) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_u8(self, _v: u8) -> Result<()> {
-        todo!()
+    fn serialize_i128(self, v: i128) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_u16(self, _v: u16) -> Result<()> {
-        todo!()
+    fn serialize_u8(self, v: u8) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_u32(self, _v: u32) -> Result<()> {
-        todo!()
+    fn serialize_u16(self, v: u16) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_u64(self, _v: u64) -> Result<()> {
-        todo!()
+    fn serialize_u32(self, v: u32) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_f32(self, _v: f32) -> Result<()> {
-        todo!()
+    fn serialize_u64(self, v: u64) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_f64(self, _v: f64) -> Result<()> {
-        todo!()
+    fn serialize_u128(self, v: u128) -> Result<String> {
+        self.serialize_integer(v)
     }
 
-    fn serialize_char(self, _v: char) -> Result<()> {
-        todo!()
+    fn serialize_f32(self, _v: f32) -> Result<String> {
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Float,
+        }))
     }
 
-    fn serialize_str(self, v: &str) -> Result<()> {
-        format_key(&mut self.ser.writer, &mut self.ser.formatter, v).map_err(Error::io)
+    fn serialize_f64(self, _v: f64) -> Result<String> {
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Float,
+        }))
     }
 
-    fn serialize_bytes(self, _v: &[u8]) -> Result<()> {
-        todo!()
+    fn serialize_char(self, v: char) -> Result<String> {
+        Ok(v.to_string())
     }
 
-    fn serialize_none(self) -> Result<()> {
-        todo!()
+    fn serialize_str(self, v: &str) -> Result<String> {
+        Ok(v.to_owned())
     }
 
-    fn serialize_some<T>(self, _value: &T) -> Result<()>
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String> {
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Bytes,
+        }))
+    }
+
+    fn serialize_none(self) -> Result<String> {
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::None,
+        }))
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<String>
     where
         T: Serialize,
         T: ?Sized,
     {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Some,
+        }))
     }
 
-    fn serialize_unit(self) -> Result<()> {
-        todo!()
+    fn serialize_unit(self) -> Result<String> {
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Unit,
+        }))
     }
 
-    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> {
-        todo!()
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String> {
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::UnitStruct,
+        }))
     }
 
     fn serialize_unit_variant(
         self,
         _name: &'static str,
         _variant_index: u32,
-        _variant: &'static str,
-    ) -> Result<()> {
-        todo!()
+        variant: &'static str,
+    ) -> Result<String> {
+        Ok(variant.to_owned())
     }
 
-    fn serialize_newtype_struct<T>(self, _name: &'static str, _value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<String>
     where
         T: Serialize,
         T: ?Sized,
     {
-        todo!()
+        if name == one_of::TOKEN || name == raw::TOKEN || name == variable::TOKEN {
+            return Err(Error::from_code(ErrorCode::KeyMustBeAName {
+                kind: Kind::NewtypeStruct,
+            }));
+        }
+
+        value.serialize(self)
     }
 
     fn serialize_newtype_variant<T>(
@@ -442,26 +788,32 @@ where
         _variant_index: u32,
         _variant: &'static str,
         _value: &T,
-    ) -> Result<()>
+    ) -> Result<String>
     where
         T: Serialize,
         T: ?Sized,
     {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::NewtypeVariant,
+        }))
     }
 
     fn serialize_seq(
         self,
         _len: Option<usize>,
     ) -> std::prelude::v1::Result<Self::SerializeSeq, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Seq,
+        }))
     }
 
     fn serialize_tuple(
         self,
         _len: usize,
     ) -> std::prelude::v1::Result<Self::SerializeTuple, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Tuple,
+        }))
     }
 
     fn serialize_tuple_struct(
@@ -469,7 +821,9 @@ where
         _name: &'static str,
         _len: usize,
     ) -> std::prelude::v1::Result<Self::SerializeTupleStruct, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::TupleStruct,
+        }))
     }
 
     fn serialize_tuple_variant(
@@ -479,14 +833,18 @@ where
         _variant: &'static str,
         _len: usize,
     ) -> std::prelude::v1::Result<Self::SerializeTupleVariant, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::TupleVariant,
+        }))
     }
 
     fn serialize_map(
         self,
         _len: Option<usize>,
     ) -> std::prelude::v1::Result<Self::SerializeMap, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Map,
+        }))
     }
 
     fn serialize_struct(
@@ -494,7 +852,9 @@ where
         _name: &'static str,
         _len: usize,
     ) -> std::prelude::v1::Result<Self::SerializeStruct, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::Struct,
+        }))
     }
 
     fn serialize_struct_variant(
@@ -504,7 +864,9 @@ where
         _variant: &'static str,
         _len: usize,
     ) -> std::prelude::v1::Result<Self::SerializeStructVariant, Self::Error> {
-        todo!()
+        Err(Error::from_code(ErrorCode::KeyMustBeAName {
+            kind: Kind::StructVariant,
+        }))
     }
 }
 
@@ -541,9 +903,19 @@ where
                     .begin_array_value(&mut ser.writer, *state == State::First)
                     .map_err(Error::io)?;
 
+                match state {
+                    State::Rest => {
+                        if let Some(PathSegment::Index(index)) = ser.path.last_mut() {
+                            *index += 1;
+                        }
+                    }
+                    _ => ser.path.push(PathSegment::Index(0)),
+                }
                 *state = State::Rest;
 
-                value.serialize(&mut **ser)?;
+                value
+                    .serialize(&mut **ser)
+                    .map_err(|err| err.with_path(&ser.path))?;
 
                 ser.formatter
                     .end_array_value(&mut ser.writer)
@@ -556,7 +928,11 @@ where
         match self {
             Compount::Map { ser, state } => match state {
                 State::Empty => Ok(()),
-                _ => ser.formatter.end_array(&mut ser.writer).map_err(Error::io),
+                State::First => ser.formatter.end_array(&mut ser.writer).map_err(Error::io),
+                State::Rest => {
+                    ser.path.pop();
+                    ser.formatter.end_array(&mut ser.writer).map_err(Error::io)
+                }
             },
         }
     }
@@ -626,11 +1002,18 @@ where
             Compount::Map { ser, state } => {
                 match state {
                     State::Empty => {}
-                    _ => ser
+                    State::First => ser
                         .formatter
                         .end_array(&mut ser.writer)
                         .map_err(Error::io)?,
+                    State::Rest => {
+                        ser.path.pop();
+                        ser.formatter
+                            .end_array(&mut ser.writer)
+                            .map_err(Error::io)?
+                    }
                 }
+                ser.path.pop();
 
                 ser.formatter
                     .end_object_value(&mut ser.writer)
@@ -662,7 +1045,18 @@ where
                     .map_err(Error::io)?;
                 *state = State::Rest;
 
-                key.serialize(MapKeySerializer { ser: *ser })?;
+                let name = key
+                    .serialize(MapKeySerializer)
+                    .map_err(|err| err.with_path(&ser.path))?;
+                format_key(
+                    &mut ser.writer,
+                    &mut ser.formatter,
+                    &name,
+                    ser.rename_policy,
+                )
+                .map_err(|err| err.with_path(&ser.path))?;
+                ser.path.push(PathSegment::Field(Cow::Owned(name)));
+
                 ser.formatter
                     .end_object_key(&mut ser.writer)
                     .map_err(Error::io)
@@ -670,6 +1064,21 @@ where
         }
     }
 
+    fn serialize_entry<K, V>(&mut self, key: &K, value: &V) -> Result<()>
+    where
+        K: Serialize,
+        K: ?Sized,
+        V: Serialize,
+        V: ?Sized,
+    {
+        if maybe_undefined::is_undefined(value) {
+            return Ok(());
+        }
+
+        self.serialize_key(key)?;
+        self.serialize_value(value)
+    }
+
     fn serialize_value<T>(&mut self, value: &T) -> std::prelude::v1::Result<(), Self::Error>
     where
         T: Serialize,
@@ -680,7 +1089,11 @@ where
                 ser.formatter
                     .begin_object_value(&mut ser.writer)
                     .map_err(Error::io)?;
-                value.serialize(&mut **ser)?;
+                value
+                    .serialize(&mut **ser)
+                    .map_err(|err| err.with_path(&ser.path))?;
+                ser.path.pop();
+
                 ser.formatter
                     .end_object_value(&mut ser.writer)
                     .map_err(Error::io)
@@ -698,6 +1111,38 @@ where
     }
 }
 
+impl<'a, W, F> Compount<'a, W, F>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    /// Writes a struct field whose value is known not to be undefined.
+    pub(crate) fn serialize_defined_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        match self {
+            Compount::Map { ser, state } => {
+                ser.formatter
+                    .begin_object_key(&mut ser.writer, *state == State::First)
+                    .map_err(Error::io)?;
+                *state = State::Rest;
+
+                format_key(&mut ser.writer, &mut ser.formatter, key, ser.rename_policy)
+                    .map_err(|err| err.with_path(&ser.path))?;
+                ser.path.push(PathSegment::Field(Cow::Borrowed(key)));
+
+                ser.formatter
+                    .end_object_key(&mut ser.writer)
+                    .map_err(Error::io)?;
+            }
+        }
+
+        serde::ser::SerializeMap::serialize_value(self, value)
+    }
+}
+
 impl<'a, W, F> serde::ser::SerializeStruct for Compount<'a, W, F>
 where
     W: io::Write,
@@ -716,9 +1161,11 @@ where
         T: Serialize,
         T: ?Sized,
     {
-        match self {
-            Compount::Map { .. } => serde::ser::SerializeMap::serialize_entry(self, key, value),
+        if maybe_undefined::is_undefined(value) {
+            return Ok(());
         }
+
+        self.serialize_defined_field(key, value)
     }
 
     fn end(self) -> Result<()> {
@@ -761,6 +1208,8 @@ where
                         .end_object(&mut ser.writer)
                         .map_err(Error::io)?,
                 }
+                ser.path.pop();
+
                 ser.formatter
                     .end_object_value(&mut ser.writer)
                     .map_err(Error::io)?;
@@ -769,3 +1218,190 @@ where
         }
     }
 }
+
+/// Collects a value that must serialize as a string, such as the name wrapped
+/// by a `Variable`. Any other data type results in an error.
+pub(crate) struct StringCollector {
+    pub(crate) context: &'static str,
+}
+
+impl StringCollector {
+    fn expected_string(&self, kind: Kind) -> Error {
+        Error::from_code(ErrorCode::ExpectedString {
+            context: self.context,
+            kind,
+        })
+    }
+}
+
+impl serde::ser::Serializer for StringCollector {
+    type Ok = String;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<String, Error>;
+    type SerializeTuple = Impossible<String, Error>;
+    type SerializeTupleStruct = Impossible<String, Error>;
+    type SerializeTupleVariant = Impossible<String, Error>;
+    type SerializeMap = Impossible<String, Error>;
+    type SerializeStruct = Impossible<String, Error>;
+    type SerializeStructVariant = Impossible<String, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<String> {
+        Err(self.expected_string(Kind::Bool))
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_i128(self, _v: i128) -> Result<String> {
+        Err(self.expected_string(Kind::I128))
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<String> {
+        Err(self.expected_string(Kind::Integer))
+    }
+
+    fn serialize_u128(self, _v: u128) -> Result<String> {
+        Err(self.expected_string(Kind::U128))
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<String> {
+        Err(self.expected_string(Kind::Float))
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<String> {
+        Err(self.expected_string(Kind::Float))
+    }
+
+    fn serialize_char(self, v: char) -> Result<String> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String> {
+        Ok(v.to_owned())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String> {
+        Err(self.expected_string(Kind::Bytes))
+    }
+
+    fn serialize_none(self) -> Result<String> {
+        Err(self.expected_string(Kind::None))
+    }
+
+    fn serialize_some<T>(self, _value: &T) -> Result<String>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Err(self.expected_string(Kind::Some))
+    }
+
+    fn serialize_unit(self) -> Result<String> {
+        Err(self.expected_string(Kind::Unit))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String> {
+        Err(self.expected_string(Kind::UnitStruct))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<String> {
+        Err(self.expected_string(Kind::UnitVariant))
+    }
+
+    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<String>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        Err(self.expected_string(Kind::NewtypeVariant))
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(self.expected_string(Kind::Seq))
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(self.expected_string(Kind::Tuple))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(self.expected_string(Kind::TupleStruct))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(self.expected_string(Kind::TupleVariant))
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(self.expected_string(Kind::Map))
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(self.expected_string(Kind::Struct))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(self.expected_string(Kind::StructVariant))
+    }
+}
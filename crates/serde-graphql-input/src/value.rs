@@ -0,0 +1,209 @@
+use std::fmt;
+use std::io;
+
+use serde::ser::{Serialize, Serializer};
+
+use crate::variable;
+use crate::{
+    format_escaped_str, CompactFormatter, Formatter, PrettyFormatter, UnicodePolicy, Variable,
+};
+
+mod de;
+mod ser;
+
+pub use ser::to_value;
+
+pub(crate) const ENUM_TOKEN: &str = "$serde_graphql_input::private::Enum";
+
+/// The fields of an object value, in insertion order.
+pub type Map = indexmap::IndexMap<String, Value>;
+
+/// Any GraphQL input value.
+///
+/// `Enum` and `Variable` hold bare names, which are checked when the value is
+/// written. Other serializers see enum values and variables as plain strings,
+/// so they come back as `String` when read from a format without them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Value {
+    #[default]
+    Null,
+    Int(i64),
+    Float(f64),
+    String(String),
+    Boolean(bool),
+    Enum(String),
+    List(Vec<Value>),
+    Object(Map),
+    Variable(String),
+}
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::Null => serializer.serialize_none(),
+            Value::Int(v) => serializer.serialize_i64(*v),
+            Value::Float(v) => serializer.serialize_f64(*v),
+            Value::String(v) => serializer.serialize_str(v),
+            Value::Boolean(v) => serializer.serialize_bool(*v),
+            Value::Enum(name) => serializer.serialize_newtype_struct(ENUM_TOKEN, name),
+            Value::List(values) => serializer.collect_seq(values),
+            Value::Object(fields) => serializer.collect_map(fields),
+            Value::Variable(name) => serializer.serialize_newtype_struct(variable::TOKEN, name),
+        }
+    }
+}
+
+/// Writes the value with the compact formatter, or with the pretty one for
+/// `{:#}`.
+///
+/// Unlike [`to_string`](crate::to_string), this never fails: integers are
+/// written as they are, non-finite floats as `null`, and names are not
+/// validated. Serialize the value to have it checked against the policies.
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut writer = WriterFormatter { inner: f };
+        let result = if writer.inner.alternate() {
+            write_value(&mut writer, &mut PrettyFormatter::new(), self)
+        } else {
+            write_value(&mut writer, &mut CompactFormatter, self)
+        };
+
+        result.map_err(|_| fmt::Error)
+    }
+}
+
+fn write_value<W, F>(writer: &mut W, formatter: &mut F, value: &Value) -> io::Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+{
+    match value {
+        Value::Null => formatter.write_null(writer),
+        Value::Int(v) => formatter.write_i64(writer, *v),
+        Value::Float(v) if v.is_finite() => formatter.write_f64(writer, *v),
+        Value::Float(_) => formatter.write_null(writer),
+        Value::String(v) => format_escaped_str(writer, formatter, v, UnicodePolicy::Raw),
+        Value::Boolean(v) => formatter.write_bool(writer, *v),
+        Value::Enum(name) => formatter.write_string(writer, name),
+        Value::Variable(name) => formatter.write_variable(writer, name),
+        Value::List(values) => {
+            formatter.begin_array(writer)?;
+            for (index, value) in values.iter().enumerate() {
+                formatter.begin_array_value(writer, index == 0)?;
+                write_value(writer, formatter, value)?;
+                formatter.end_array_value(writer)?;
+            }
+            formatter.end_array(writer)
+        }
+        Value::Object(fields) => {
+            formatter.begin_object(writer)?;
+            for (index, (key, value)) in fields.iter().enumerate() {
+                formatter.begin_object_key(writer, index == 0)?;
+                formatter.write_string(writer, key)?;
+                formatter.end_object_key(writer)?;
+                formatter.begin_object_value(writer)?;
+                write_value(writer, formatter, value)?;
+                formatter.end_object_value(writer)?;
+            }
+            formatter.end_object(writer)
+        }
+    }
+}
+
+struct WriterFormatter<'a, 'b: 'a> {
+    inner: &'a mut fmt::Formatter<'b>,
+}
+
+impl<'a, 'b> io::Write for WriterFormatter<'a, 'b> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The serializer only ever writes whole UTF-8 characters.
+        let s = std::str::from_utf8(buf).map_err(io::Error::other)?;
+        self.inner.write_str(s).map_err(io::Error::other)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Boolean(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Int(value.into())
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_owned())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl<T> From<Vec<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Vec<T>) -> Self {
+        Value::List(value.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<Map> for Value {
+    fn from(value: Map) -> Self {
+        Value::Object(value)
+    }
+}
+
+impl<S> From<Variable<S>> for Value
+where
+    S: AsRef<str>,
+{
+    fn from(value: Variable<S>) -> Self {
+        Value::Variable(value.name().to_owned())
+    }
+}
+
+impl<T> From<Option<T>> for Value
+where
+    T: Into<Value>,
+{
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => value.into(),
+            None => Value::Null,
+        }
+    }
+}
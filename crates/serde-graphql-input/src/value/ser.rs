@@ -0,0 +1,422 @@
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use serde::ser::{self, Serialize};
+
+use super::{Map, Value, ENUM_TOKEN};
+use crate::error::{Error, ErrorCode, Result};
+use crate::maybe_undefined;
+use crate::one_of;
+use crate::raw;
+use crate::variable;
+use crate::{MapKeySerializer, Serializer, StringCollector};
+
+/// Converts `value` into a `Value`.
+///
+/// The conversion follows the default policies of the `Serializer`: byte
+/// arrays become base64 strings, integers must fit in an `i64` and unit
+/// variants become enum values without renaming. Names are only checked once
+/// the `Value` is written.
+pub fn to_value<T>(value: &T) -> Result<Value>
+where
+    T: ?Sized + Serialize,
+{
+    value.serialize(ValueSerializer)
+}
+
+struct ValueSerializer;
+
+impl ValueSerializer {
+    fn serialize_integer<I>(self, value: I) -> Result<Value>
+    where
+        I: Copy + TryInto<i64> + itoa::Integer,
+    {
+        match value.try_into() {
+            Ok(value) => Ok(Value::Int(value)),
+            Err(_) => {
+                let mut buffer = itoa::Buffer::new();
+                Err(Error::from_code(ErrorCode::IntOutOfRange {
+                    value: buffer.format(value).into(),
+                }))
+            }
+        }
+    }
+}
+
+impl ser::Serializer for ValueSerializer {
+    type Ok = Value;
+    type Error = Error;
+
+    type SerializeSeq = SerializeList;
+    type SerializeTuple = SerializeList;
+    type SerializeTupleStruct = SerializeList;
+    type SerializeTupleVariant = SerializeTupleVariant;
+    type SerializeMap = SerializeObject;
+    type SerializeStruct = SerializeObject;
+    type SerializeStructVariant = SerializeStructVariant;
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Boolean(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Value> {
+        Ok(Value::Int(v))
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Value> {
+        self.serialize_integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Value> {
+        Ok(Value::Int(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Value> {
+        self.serialize_integer(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Value> {
+        self.serialize_integer(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        Ok(Value::Float(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        Ok(Value::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Value> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Value> {
+        Ok(Value::String(BASE64_STANDARD.encode(v)))
+    }
+
+    fn serialize_none(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_some<T>(self, value: &T) -> Result<Value>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Value> {
+        Ok(Value::Null)
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Value> {
+        Ok(Value::Enum(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        if name == ENUM_TOKEN {
+            return value
+                .serialize(StringCollector {
+                    context: "enum value",
+                })
+                .map(Value::Enum);
+        }
+
+        if name == variable::TOKEN {
+            return value
+                .serialize(StringCollector {
+                    context: "variable name",
+                })
+                .map(Value::Variable);
+        }
+
+        // Both are checked while they are written, so write them and read
+        // the result back.
+        if name == one_of::TOKEN || name == raw::TOKEN {
+            let mut writer = Vec::with_capacity(128);
+            ser::Serializer::serialize_newtype_struct(
+                &mut Serializer::new(&mut writer),
+                name,
+                value,
+            )?;
+            return crate::from_slice(&writer);
+        }
+
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Value>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        let mut fields = Map::with_capacity(1);
+        fields.insert(variant.to_owned(), to_value(value)?);
+        Ok(Value::Object(fields))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Ok(SerializeList {
+            values: Vec::with_capacity(len.unwrap_or(0)),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Ok(SerializeTupleVariant {
+            variant,
+            values: Vec::with_capacity(len),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap> {
+        Ok(SerializeObject {
+            fields: Map::with_capacity(len.unwrap_or(0)),
+            next_key: None,
+        })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Ok(SerializeStructVariant {
+            variant,
+            fields: Map::with_capacity(len),
+        })
+    }
+}
+
+struct SerializeList {
+    values: Vec<Value>,
+}
+
+impl ser::SerializeSeq for SerializeList {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        self.values.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::List(self.values))
+    }
+}
+
+impl ser::SerializeTuple for SerializeList {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_element<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+impl ser::SerializeTupleStruct for SerializeList {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeSeq::end(self)
+    }
+}
+
+struct SerializeTupleVariant {
+    variant: &'static str,
+    values: Vec<Value>,
+}
+
+impl ser::SerializeTupleVariant for SerializeTupleVariant {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        self.values.push(to_value(value)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        let mut fields = Map::with_capacity(1);
+        fields.insert(self.variant.to_owned(), Value::List(self.values));
+        Ok(Value::Object(fields))
+    }
+}
+
+struct SerializeObject {
+    fields: Map,
+    next_key: Option<String>,
+}
+
+impl ser::SerializeMap for SerializeObject {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_key<T>(&mut self, key: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        self.next_key = Some(key.serialize(MapKeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T>(&mut self, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        let key = self
+            .next_key
+            .take()
+            .expect("serialize_value called before serialize_key");
+
+        if !maybe_undefined::is_undefined(value) {
+            self.fields.insert(key, to_value(value)?);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Object(self.fields))
+    }
+}
+
+impl ser::SerializeStruct for SerializeObject {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        if !maybe_undefined::is_undefined(value) {
+            self.fields.insert(key.to_owned(), to_value(value)?);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        ser::SerializeMap::end(self)
+    }
+}
+
+struct SerializeStructVariant {
+    variant: &'static str,
+    fields: Map,
+}
+
+impl ser::SerializeStructVariant for SerializeStructVariant {
+    type Ok = Value;
+    type Error = Error;
+
+    fn serialize_field<T>(&mut self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: Serialize,
+        T: ?Sized,
+    {
+        if !maybe_undefined::is_undefined(value) {
+            self.fields.insert(key.to_owned(), to_value(value)?);
+        }
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        let mut fields = Map::with_capacity(1);
+        fields.insert(self.variant.to_owned(), Value::Object(self.fields));
+        Ok(Value::Object(fields))
+    }
+}
@@ -0,0 +1,76 @@
+use std::fmt;
+
+use serde::de::{Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+pub(crate) const TOKEN: &str = "$serde_graphql_input::private::Variable";
+
+/// A reference to an operation variable, written as a bare `$name`.
+///
+/// `Variable("id")` serializes to `$id` instead of the quoted string `"id"`.
+/// The name must be a valid GraphQL `Name`. Other serializers see a plain
+/// newtype struct around the name.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Variable<S = String>(pub S);
+
+impl<S> Variable<S>
+where
+    S: AsRef<str>,
+{
+    pub fn name(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+impl<S> fmt::Display for Variable<S>
+where
+    S: AsRef<str>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "${}", self.name())
+    }
+}
+
+impl<S> Serialize for Variable<S>
+where
+    S: AsRef<str>,
+{
+    fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        serializer.serialize_newtype_struct(TOKEN, self.name())
+    }
+}
+
+impl<'de, S> Deserialize<'de> for Variable<S>
+where
+    S: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct VariableVisitor<S>(std::marker::PhantomData<S>);
+
+        impl<'de, S> Visitor<'de> for VariableVisitor<S>
+        where
+            S: Deserialize<'de>,
+        {
+            type Value = Variable<S>;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a variable reference")
+            }
+
+            fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                S::deserialize(deserializer).map(Variable)
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(TOKEN, VariableVisitor(std::marker::PhantomData))
+    }
+}
@@ -0,0 +1,165 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_graphql_input::{ArgumentsSerializer, MaybeUndefined, Serializer};
+
+#[derive(Serialize)]
+struct Filter {
+    name: &'static str,
+    limit: u32,
+}
+
+#[derive(Serialize)]
+struct Args {
+    id: u32,
+    filter: Filter,
+    tags: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct Empty {}
+
+#[derive(Serialize)]
+struct Wrapper(Filter);
+
+#[derive(Serialize)]
+struct Optional {
+    first: MaybeUndefined<u32>,
+    after: MaybeUndefined<&'static str>,
+}
+
+#[test]
+fn writes_structs_as_arguments() {
+    let args = Args {
+        id: 1,
+        filter: Filter {
+            name: "a",
+            limit: 10,
+        },
+        tags: vec!["x", "y"],
+    };
+
+    let output = serde_graphql_input::to_arguments_string(&args).unwrap();
+
+    assert_eq!(
+        r#"(id:1,filter:{name:"a",limit:10},tags:["x","y"])"#,
+        output
+    );
+}
+
+#[test]
+fn writes_maps_as_arguments() {
+    let mut args = BTreeMap::new();
+    args.insert("first", 10);
+    args.insert("offset", 20);
+
+    let output = serde_graphql_input::to_arguments_string(&args).unwrap();
+
+    assert_eq!("(first:10,offset:20)", output);
+}
+
+#[test]
+fn writes_nothing_without_arguments() {
+    let empty = serde_graphql_input::to_arguments_string(&Empty {}).unwrap();
+    let map = serde_graphql_input::to_arguments_string(&BTreeMap::<String, u32>::new()).unwrap();
+
+    assert_eq!("", empty);
+    assert_eq!("", map);
+}
+
+#[test]
+fn looks_through_newtype_structs() {
+    let output = serde_graphql_input::to_arguments_string(&Wrapper(Filter {
+        name: "a",
+        limit: 1,
+    }))
+    .unwrap();
+
+    assert_eq!(r#"(name:"a",limit:1)"#, output);
+}
+
+#[test]
+fn skips_undefined_arguments() {
+    let cases = [
+        (MaybeUndefined::Undefined, MaybeUndefined::Undefined, ""),
+        (
+            MaybeUndefined::Value(5),
+            MaybeUndefined::Undefined,
+            "(first:5)",
+        ),
+        (
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Value("abc"),
+            r#"(after:"abc")"#,
+        ),
+        (
+            MaybeUndefined::Null,
+            MaybeUndefined::Null,
+            "(first:null,after:null)",
+        ),
+    ];
+
+    for (first, after, expected) in cases {
+        let output = serde_graphql_input::to_arguments_string(&Optional { first, after }).unwrap();
+        assert_eq!(expected, output);
+    }
+
+    let mut map = BTreeMap::new();
+    map.insert("a", MaybeUndefined::Undefined);
+    map.insert("b", MaybeUndefined::Value(1));
+
+    let output = serde_graphql_input::to_arguments_string(&map).unwrap();
+    assert_eq!("(b:1)", output);
+}
+
+#[test]
+fn can_pretty_print_arguments() {
+    let args = Args {
+        id: 1,
+        filter: Filter {
+            name: "a",
+            limit: 10,
+        },
+        tags: vec![],
+    };
+
+    let mut writer = Vec::new();
+    let mut ser = Serializer::pretty(&mut writer);
+    args.serialize(ArgumentsSerializer::new(&mut ser)).unwrap();
+
+    assert_eq!(
+        "(\n  id: 1,\n  filter: {\n    name: \"a\",\n    limit: 10\n  },\n  tags: []\n)",
+        String::from_utf8(writer).unwrap()
+    );
+}
+
+#[test]
+fn rejects_other_values() {
+    let int = serde_graphql_input::to_arguments_string(&1).unwrap_err();
+    let list = serde_graphql_input::to_arguments_string(&[1, 2]).unwrap_err();
+    let string = serde_graphql_input::to_arguments_string("a").unwrap_err();
+
+    assert_eq!(
+        "arguments must be a struct or map, found integer",
+        int.to_string()
+    );
+    assert_eq!(
+        "arguments must be a struct or map, found tuple",
+        list.to_string()
+    );
+    assert_eq!(
+        "arguments must be a struct or map, found string",
+        string.to_string()
+    );
+    assert!(int.is_data());
+}
+
+#[test]
+fn reports_the_path_of_invalid_arguments() {
+    let mut args = BTreeMap::new();
+    args.insert("ratio", f64::NAN);
+
+    let err = serde_graphql_input::to_arguments_string(&args).unwrap_err();
+
+    assert_eq!("at ratio: `NaN` is not a finite float", err.to_string());
+}
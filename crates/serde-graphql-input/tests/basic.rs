@@ -13,7 +13,7 @@ fn can_serialize_simple_struct() {
         something: "Something".into(),
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"{something:"Something"}"#, output.as_str())
 }
@@ -32,7 +32,7 @@ fn can_handle_multiple_values() {
         something_else: "else".into(),
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(
         r#"{something:"Something",somethingElse:"else"}"#,
@@ -56,7 +56,7 @@ fn can_handle_embedded_structs() {
         item: "some item".into(),
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(
         r#"{nested:{nested:null,item:"some nested item"},item:"some item"}"#,
@@ -81,7 +81,7 @@ fn can_handle_embedded_structs_omit() {
         item: "some item".into(),
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(
         r#"{nested:{item:"some nested item"},item:"some item"}"#,
@@ -100,7 +100,7 @@ fn can_handle_array() {
         items: vec!["one".into(), "two".into(), "three".into(), "four".into()],
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"{items:["one","two","three","four"]}"#, output.as_str())
 }
@@ -128,7 +128,7 @@ fn can_handle_mixed_items() {
         ],
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(
         r#"{items:["something",{item:"something"}]}"#,
@@ -153,7 +153,7 @@ fn can_handle_enums() {
         items: vec![VariantEnum::ItemA, VariantEnum::ItemB],
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"{items:[ItemA,ItemB]}"#, output.as_str())
 }
@@ -169,7 +169,7 @@ fn can_handle_tuples() {
         items: ("one".into(), "two".into()),
     };
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"{items:["one","two"]}"#, output.as_str())
 }
@@ -181,7 +181,7 @@ fn can_handle_newtype_struct() {
 
     let input = Input("something".into());
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#""something""#, output.as_str())
 }
@@ -190,7 +190,7 @@ fn can_handle_newtype_struct() {
 fn can_handle_i64() {
     let input = 42_i64;
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"42"#, output.as_str())
 }
@@ -199,7 +199,7 @@ fn can_handle_i64() {
 fn can_handle_bool() {
     let input = true;
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"true"#, output.as_str())
 }
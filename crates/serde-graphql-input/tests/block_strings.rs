@@ -0,0 +1,136 @@
+use serde::Serialize;
+use serde_graphql_input::StringPolicy;
+
+mod common;
+
+fn to_block_string(value: &str) -> String {
+    common::to_string_with(&value, |ser| {
+        ser.with_string_policy(StringPolicy::Block { threshold: 0 })
+    })
+    .unwrap()
+}
+
+#[test]
+fn writes_quoted_strings_by_default() {
+    let output = serde_graphql_input::to_string(&"one\ntwo").unwrap();
+
+    assert_eq!(r#""one\ntwo""#, output.as_str())
+}
+
+#[test]
+fn writes_multi_line_strings_as_block_strings() {
+    #[derive(Serialize)]
+    struct Input {
+        body: &'static str,
+        title: &'static str,
+    }
+
+    let input = Input {
+        body: "# Title\n\n  - \"quoted\" item\n\tend",
+        title: "single line",
+    };
+    let output = common::to_string_with(&input, |ser| {
+        ser.with_string_policy(StringPolicy::Block { threshold: 0 })
+    })
+    .unwrap();
+
+    assert_eq!(
+        "{body:\"\"\"\n# Title\n\n  - \"quoted\" item\n\tend\n\"\"\",title:\"single line\"}",
+        output.as_str()
+    )
+}
+
+#[test]
+fn only_writes_block_strings_from_threshold() {
+    let policy = StringPolicy::Block { threshold: 8 };
+
+    assert_eq!(
+        r#""a\nb""#,
+        common::to_string_with(&"a\nb", |ser| ser.with_string_policy(policy))
+            .unwrap()
+            .as_str()
+    );
+    assert_eq!(
+        "\"\"\"\nabc\nefgh\n\"\"\"",
+        common::to_string_with(&"abc\nefgh", |ser| ser.with_string_policy(policy))
+            .unwrap()
+            .as_str()
+    );
+    assert_eq!(
+        r#""no line breaks at all""#,
+        common::to_string_with(&"no line breaks at all", |ser| ser
+            .with_string_policy(policy))
+        .unwrap()
+        .as_str()
+    );
+}
+
+#[test]
+fn escapes_triple_quotes() {
+    assert_eq!(
+        "\"\"\"\na \\\"\"\" b\n\\\"\"\"\"\"\n\"\"\"",
+        to_block_string("a \"\"\" b\n\"\"\"\"\"").as_str()
+    );
+}
+
+#[test]
+fn falls_back_to_quoted_strings_that_would_change() {
+    let cases = [
+        ("\nleading blank line", r#""\nleading blank line""#),
+        ("  \nleading blank line", r#""  \nleading blank line""#),
+        ("trailing blank line\n", r#""trailing blank line\n""#),
+        ("trailing blank line\n\t", r#""trailing blank line\n\t""#),
+        ("  common\n  indent", r#""  common\n  indent""#),
+        ("windows\r\nline ending", r#""windows\r\nline ending""#),
+        ("control\ncharacter\u{1}", r#""control\ncharacter\u0001""#),
+    ];
+
+    for (value, expected) in cases {
+        assert_eq!(expected, to_block_string(value).as_str(), "{value:?}");
+    }
+}
+
+#[test]
+fn block_strings_round_trip() {
+    let cases = [
+        "one\ntwo",
+        "first\n  indented\n    more",
+        "  indented first\nnot second",
+        "a\n\n\nb",
+        "a\n   \nb",
+        "ends with quote\n\"",
+        "ends with quotes\n\"\"",
+        "ends with backslash\n\\",
+        "\"\"\"\n\"\"\"",
+        "\\\"\"\"\nescaped",
+        "\"\"\"\"\"\nfive quotes",
+        "unicode æ 😀\n\ttab",
+        "\nleading",
+        "trailing\n",
+        "  common\n  indent",
+        "carriage\rreturn",
+    ];
+
+    for value in cases {
+        let output = to_block_string(value);
+        let roundtrip: String = serde_graphql_input::from_str(&output).unwrap();
+
+        assert_eq!(value, roundtrip, "{output}");
+    }
+}
+
+#[test]
+fn can_deserialize_block_strings() {
+    let output: String = serde_graphql_input::from_str(
+        "\"\"\"\n\n    Hello,\r\n      World!\r\n\n    Yours,\n      GraphQL.\n  \n\"\"\"",
+    )
+    .unwrap();
+
+    assert_eq!("Hello,\n  World!\n\nYours,\n  GraphQL.", output);
+
+    let output: String = serde_graphql_input::from_str(r#""""  raw \n \"""  """"#).unwrap();
+    assert_eq!(r#"  raw \n """  "#, output);
+
+    let output: Vec<String> = serde_graphql_input::from_str(r#"["""""", """ """]"#).unwrap();
+    assert_eq!(vec![String::new(), String::new()], output);
+}
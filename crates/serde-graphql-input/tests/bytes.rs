@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_graphql_input::BytesPolicy;
+
+mod common;
+
+#[derive(Serialize, Clone, Debug)]
+struct Input {
+    #[serde(with = "serde_bytes")]
+    payload: Vec<u8>,
+}
+
+#[test]
+fn serializes_bytes_as_base64_by_default() {
+    let input = Input {
+        payload: vec![0xfb, 0xff, 0x68, 0x69],
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(r#"{payload:"+/9oaQ=="}"#, output.as_str())
+}
+
+#[test]
+fn can_serialize_bytes_as_url_safe_base64() {
+    let input = Input {
+        payload: vec![0xfb, 0xff, 0x68, 0x69],
+    };
+
+    let output = common::to_string_with(&input, |ser| {
+        ser.with_bytes_policy(BytesPolicy::Base64UrlSafe)
+    })
+    .unwrap();
+
+    assert_eq!(r#"{payload:"-_9oaQ"}"#, output.as_str())
+}
+
+#[test]
+fn can_serialize_bytes_as_hex() {
+    let input = Input {
+        payload: vec![0x00, 0x0f, 0xab, 0xff],
+    };
+
+    let output =
+        common::to_string_with(&input, |ser| ser.with_bytes_policy(BytesPolicy::Hex)).unwrap();
+
+    assert_eq!(r#"{payload:"000fabff"}"#, output.as_str())
+}
+
+#[test]
+fn can_serialize_bytes_as_list() {
+    let input = Input {
+        payload: vec![1, 2, 255],
+    };
+    let output =
+        common::to_string_with(&input, |ser| ser.with_bytes_policy(BytesPolicy::List)).unwrap();
+    assert_eq!(r#"{payload:[1,2,255]}"#, output.as_str());
+
+    let input = Input { payload: vec![] };
+    let output =
+        common::to_string_with(&input, |ser| ser.with_bytes_policy(BytesPolicy::List)).unwrap();
+    assert_eq!(r#"{payload:[]}"#, output.as_str());
+}
+
+#[test]
+fn can_forbid_bytes() {
+    let input = Input {
+        payload: vec![1, 2, 3],
+    };
+
+    let err = common::to_string_with(&input, |ser| ser.with_bytes_policy(BytesPolicy::Error))
+        .unwrap_err();
+
+    assert_eq!(
+        "at payload: bytes are not allowed by the serializer's bytes policy",
+        err.to_string()
+    )
+}
+
+#[test]
+fn rejects_bytes_as_map_keys() {
+    let mut input = BTreeMap::new();
+    input.insert(serde_bytes::ByteBuf::from(vec![1, 2]), 1);
+
+    let err = serde_graphql_input::to_string(&input).unwrap_err();
+
+    assert_eq!("key must be a GraphQL name, found bytes", err.to_string())
+}
@@ -0,0 +1,15 @@
+use serde::Serialize;
+use serde_graphql_input::Serializer;
+
+/// Serializes `value` with a compact `Serializer` set up by `configure`, such
+/// as `|ser| ser.with_bytes_policy(BytesPolicy::Hex)`.
+pub fn to_string_with<T, C>(value: &T, configure: C) -> serde_graphql_input::error::Result<String>
+where
+    T: ?Sized + Serialize,
+    C: FnOnce(Serializer<&mut Vec<u8>>) -> Serializer<&mut Vec<u8>>,
+{
+    let mut writer = Vec::new();
+    let mut ser = configure(Serializer::new(&mut writer));
+    value.serialize(&mut ser)?;
+    Ok(String::from_utf8(writer).unwrap())
+}
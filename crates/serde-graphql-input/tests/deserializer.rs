@@ -0,0 +1,230 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn can_deserialize_simple_struct() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Input {
+        something: String,
+    }
+
+    let output: Input = serde_graphql_input::from_str(r#"{something:"Something"}"#).unwrap();
+
+    assert_eq!(
+        Input {
+            something: "Something".into()
+        },
+        output
+    )
+}
+
+#[test]
+fn can_deserialize_primitives() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Input {
+        a: i8,
+        b: u64,
+        c: i64,
+        d: f32,
+        e: f64,
+        f: bool,
+        g: char,
+        h: Option<String>,
+    }
+
+    let output: Input = serde_graphql_input::from_str(
+        r#"{a:-12,b:18446744073709551615,c:-9223372036854775808,d:123.5,e:1e3,f:true,g:"a",h:null}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        Input {
+            a: -12,
+            b: u64::MAX,
+            c: i64::MIN,
+            d: 123.5,
+            e: 1000.0,
+            f: true,
+            g: 'a',
+            h: None,
+        },
+        output
+    )
+}
+
+#[test]
+fn can_deserialize_escaped_strings() {
+    let output: String =
+        serde_graphql_input::from_str(r#""quote \" slash \\ \/ \b\f\n\r\t æ 😀""#).unwrap();
+
+    assert_eq!("quote \" slash \\ / \x08\x0c\n\r\t æ 😀", output)
+}
+
+#[test]
+fn can_deserialize_enums() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    enum VariantEnum {
+        ItemA,
+        ItemB,
+        T(u8, String),
+        S { item: String },
+        N(String),
+    }
+
+    let output: Vec<VariantEnum> = serde_graphql_input::from_str(
+        r#"[ItemA,ItemB,{"T":[123,"something"]},{S:{item:"x"}},{N:"y"}]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            VariantEnum::ItemA,
+            VariantEnum::ItemB,
+            VariantEnum::T(123, "something".into()),
+            VariantEnum::S { item: "x".into() },
+            VariantEnum::N("y".into()),
+        ],
+        output
+    )
+}
+
+#[test]
+fn can_deserialize_map() {
+    let output: BTreeMap<String, Vec<u32>> =
+        serde_graphql_input::from_str(r#"{a:[1,2],b:[]}"#).unwrap();
+
+    assert_eq!(
+        BTreeMap::from([("a".to_string(), vec![1, 2]), ("b".to_string(), vec![])]),
+        output
+    )
+}
+
+#[test]
+fn ignores_whitespace_commas_and_comments() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Input {
+        items: Vec<String>,
+        other: u8,
+    }
+
+    let output: Input = serde_graphql_input::from_str(
+        "{\n  # the items\n  items: [\"one\" \"two\"],\n  other : 1\n}\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        Input {
+            items: vec!["one".into(), "two".into()],
+            other: 1,
+        },
+        output
+    )
+}
+
+#[test]
+fn can_deserialize_from_reader() {
+    let output: Vec<bool> = serde_graphql_input::from_reader(&b"[true,false]"[..]).unwrap();
+
+    assert_eq!(vec![true, false], output)
+}
+
+#[test]
+fn can_roundtrip_serialized_input() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    enum Status {
+        Active,
+        Inactive,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+    struct Input {
+        #[serde(rename = "someName")]
+        some_name: String,
+        nested: Option<Box<Input>>,
+        tags: Vec<Status>,
+        pair: (u8, String),
+    }
+
+    let input = Input {
+        some_name: "outer \"quoted\"\n".into(),
+        nested: Some(Box::new(Input {
+            some_name: "inner".into(),
+            nested: None,
+            tags: vec![],
+            pair: (1, "one".into()),
+        })),
+        tags: vec![Status::Active, Status::Inactive],
+        pair: (2, "two".into()),
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    let roundtrip: Input = serde_graphql_input::from_str(&output).unwrap();
+
+    assert_eq!(input, roundtrip);
+
+    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let roundtrip: Input = serde_graphql_input::from_str(&output).unwrap();
+
+    assert_eq!(input, roundtrip)
+}
+
+#[test]
+fn reports_syntax_errors() {
+    let err = serde_graphql_input::from_str::<Vec<u8>>("[1,2").unwrap_err();
+    assert_eq!(
+        "EOF while parsing a value at line 1 column 4",
+        err.to_string()
+    );
+
+    let err = serde_graphql_input::from_str::<Vec<u8>>("[1] 2").unwrap_err();
+    assert_eq!("trailing characters at line 1 column 5", err.to_string());
+
+    let err = serde_graphql_input::from_str::<BTreeMap<String, u8>>("{\n a 1}").unwrap_err();
+    assert_eq!("expected `:` at line 2 column 4", err.to_string());
+
+    let err = serde_graphql_input::from_str::<u8>("01").unwrap_err();
+    assert_eq!("invalid number at line 1 column 2", err.to_string());
+}
+
+#[test]
+fn rejects_ints_beyond_64_bits() {
+    use serde_graphql_input::Value;
+
+    let err = serde_graphql_input::from_str::<Value>("99999999999999999999").unwrap_err();
+    assert_eq!(
+        "`99999999999999999999` is out of range for a GraphQL Int at line 1 column 20",
+        err.to_string()
+    );
+    assert!(err.is_data());
+
+    let err = serde_graphql_input::from_str::<Value>("-9223372036854775809").unwrap_err();
+    assert!(err.is_data());
+
+    let value = serde_graphql_input::from_str::<Value>("[-9223372036854775808, 1e20]").unwrap();
+    assert_eq!(
+        Value::List(vec![Value::Int(i64::MIN), Value::Float(1e20)]),
+        value
+    );
+}
+
+#[test]
+fn rejects_deeply_nested_input() {
+    let input = "[".repeat(200_000);
+    let err = serde_graphql_input::from_str::<serde_graphql_input::Value>(&input).unwrap_err();
+    assert_eq!(
+        "recursion limit exceeded at line 1 column 128",
+        err.to_string()
+    );
+    assert!(err.is_syntax());
+
+    let input = "{a:".repeat(200_000);
+    let err = serde_graphql_input::from_str::<serde::de::IgnoredAny>(&input).unwrap_err();
+    assert_eq!(
+        "recursion limit exceeded at line 1 column 382",
+        err.to_string()
+    );
+
+    let input = format!("{}1{}", "[".repeat(127), "]".repeat(127));
+    serde_graphql_input::from_str::<serde::de::IgnoredAny>(&input).unwrap();
+}
@@ -0,0 +1,183 @@
+use serde_graphql_input::document::{Field, Operation, OperationType};
+use serde_graphql_input::{graphql_input, Variable};
+
+fn get_user() -> Operation {
+    Operation::query()
+        .name("GetUser")
+        .variable("id", "ID!")
+        .variable_with_default("first", "Int", 10)
+        .field(
+            Field::new("user")
+                .argument("id", Variable("id"))
+                .field("id")
+                .field(
+                    Field::new("friends")
+                        .argument("first", Variable("first"))
+                        .field("name"),
+                ),
+        )
+}
+
+#[test]
+fn renders_compact_operations() {
+    assert_eq!(
+        "query GetUser($id:ID!,$first:Int=10){user(id:$id){id friends(first:$first){name}}}",
+        get_user().render().unwrap()
+    );
+}
+
+#[test]
+fn renders_pretty_operations() {
+    let expected = "\
+query GetUser(
+  $id: ID!,
+  $first: Int = 10
+) {
+  user(
+    id: $id
+  ) {
+    id
+    friends(
+      first: $first
+    ) {
+      name
+    }
+  }
+}";
+
+    assert_eq!(expected, get_user().render_pretty().unwrap());
+}
+
+#[test]
+fn renders_anonymous_operations() {
+    assert_eq!(
+        "query{viewer}",
+        Operation::query().field("viewer").render().unwrap()
+    );
+    assert_eq!(
+        "subscription{messages{body}}",
+        Operation::new(OperationType::Subscription)
+            .field(Field::new("messages").field("body"))
+            .render()
+            .unwrap()
+    );
+}
+
+#[test]
+fn renders_aliases_and_object_arguments() {
+    let operation = Operation::mutation().field(
+        Field::new("createUser")
+            .alias("created")
+            .argument(
+                "input",
+                graphql_input!({ name: "a", role: ADMIN, tags: [] }),
+            )
+            .field("id"),
+    );
+
+    assert_eq!(
+        r#"mutation{created:createUser(input:{name:"a",role:ADMIN,tags:[]}){id}}"#,
+        operation.render().unwrap()
+    );
+}
+
+#[test]
+fn renders_object_defaults() {
+    let operation = Operation::query()
+        .variable_with_default(
+            "filter",
+            "[UserFilter!]",
+            graphql_input!([{ active: true }]),
+        )
+        .field(Field::new("users").argument("filter", Variable("filter")));
+
+    assert_eq!(
+        "query($filter:[UserFilter!]=[{active:true}]){users(filter:$filter)}",
+        operation.render().unwrap()
+    );
+}
+
+#[test]
+fn rejects_variables_in_default_values() {
+    let err = Operation::query()
+        .variable_with_default("x", "Int", Variable("y"))
+        .field("a")
+        .render()
+        .unwrap_err();
+    assert_eq!(
+        "at $x: default values must be constant, found `$y`",
+        err.to_string()
+    );
+    assert!(err.is_data());
+
+    let err = Operation::query()
+        .variable_with_default("filter", "Filter", graphql_input!({ ids: [1, $id] }))
+        .field("a")
+        .render()
+        .unwrap_err();
+    assert_eq!(
+        "at $filter: default values must be constant, found `$id`",
+        err.to_string()
+    );
+}
+
+#[test]
+fn rejects_invalid_types() {
+    for ty in ["", "Int!!", "[Int", "Int]", "[]", "[Int]]", "Int Int"] {
+        let err = Operation::query()
+            .variable("a", ty)
+            .field("b")
+            .render()
+            .unwrap_err();
+
+        assert_eq!(
+            format!("`{ty}` is not a valid GraphQL type"),
+            err.to_string()
+        );
+        assert!(err.is_data());
+    }
+}
+
+#[test]
+fn rejects_invalid_names() {
+    let err = Operation::query()
+        .field(Field::new("user").field("not-a-name"))
+        .render()
+        .unwrap_err();
+    assert_eq!(
+        "at user: `not-a-name` is not a valid GraphQL name",
+        err.to_string()
+    );
+
+    let err = Operation::query()
+        .variable("1st", "Int")
+        .field("a")
+        .render()
+        .unwrap_err();
+    assert_eq!("`1st` is not a valid GraphQL name", err.to_string());
+}
+
+#[test]
+fn reports_the_path_of_invalid_arguments() {
+    let err = Operation::query()
+        .field(
+            Field::new("user")
+                .alias("me")
+                .field(Field::new("posts").argument("first", graphql_input!((f64::NAN)))),
+        )
+        .render()
+        .unwrap_err();
+
+    assert!(err.to_string().starts_with("at me.posts.first: "));
+}
+
+#[test]
+fn rejects_empty_selection_sets() {
+    let err = Operation::query().name("Empty").render().unwrap_err();
+
+    assert_eq!(
+        "an operation must select at least one field",
+        err.to_string()
+    );
+    assert!(err.is_data());
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_graphql_input::OneOf;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct UnitStruct;
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct NewtypeStruct(Vec<u8>);
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct TupleStruct(u8, u8);
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct Struct {
+    a: u8,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Enum {
+    Unit,
+    Newtype(u8),
+    Tuple(u8, u8),
+    Struct { a: u8 },
+}
+
+fn map_key_error<K>(key: K) -> String
+where
+    K: Serialize + Ord,
+{
+    serde_graphql_input::to_string(&BTreeMap::from([(key, 0)]))
+        .unwrap_err()
+        .to_string()
+}
+
+fn one_of_error<T>(value: T) -> String
+where
+    T: Serialize,
+{
+    serde_graphql_input::to_string(&OneOf(value))
+        .unwrap_err()
+        .to_string()
+}
+
+#[test]
+fn unsupported_map_keys_are_errors() {
+    let err = "key must be a GraphQL name, found";
+
+    assert_eq!(
+        format!("{err} bytes"),
+        map_key_error(serde_bytes::ByteBuf::from(vec![1]))
+    );
+    assert_eq!(format!("{err} none"), map_key_error(Option::<String>::None));
+    assert_eq!(format!("{err} option"), map_key_error(Some("a")));
+    assert_eq!(format!("{err} unit"), map_key_error(()));
+    assert_eq!(format!("{err} unit struct"), map_key_error(UnitStruct));
+    assert_eq!(
+        format!("{err} newtype variant"),
+        map_key_error(Enum::Newtype(1))
+    );
+    assert_eq!(format!("{err} sequence"), map_key_error(vec![1]));
+    assert_eq!(format!("{err} tuple"), map_key_error((1, 2)));
+    assert_eq!(
+        format!("{err} tuple struct"),
+        map_key_error(TupleStruct(1, 2))
+    );
+    assert_eq!(
+        format!("{err} tuple variant"),
+        map_key_error(Enum::Tuple(1, 2))
+    );
+    assert_eq!(
+        format!("{err} map"),
+        map_key_error(BTreeMap::from([(1, 2)]))
+    );
+    assert_eq!(format!("{err} struct"), map_key_error(Struct { a: 1 }));
+    assert_eq!(
+        format!("{err} struct variant"),
+        map_key_error(Enum::Struct { a: 1 })
+    );
+    assert_eq!(
+        format!("{err} sequence"),
+        map_key_error(NewtypeStruct(vec![1]))
+    );
+}
+
+#[test]
+fn map_keys_with_unnamed_values_are_errors() {
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_i8));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_i16));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_i32));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_i64));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_i128));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_u8));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_u16));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_u32));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_u64));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error(1_u128));
+    assert_eq!("`1` is not a valid GraphQL name", map_key_error('1'));
+    assert_eq!("`` is not a valid GraphQL name", map_key_error(""));
+}
+
+#[test]
+fn float_map_keys_are_errors() {
+    struct FloatKey(f64);
+
+    impl Serialize for FloatKey {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_f64(self.0)
+        }
+    }
+
+    struct FloatMap;
+
+    impl Serialize for FloatMap {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            use serde::ser::SerializeMap;
+
+            let mut map = serializer.serialize_map(Some(1))?;
+            map.serialize_entry(&FloatKey(1.5), &0)?;
+            map.end()
+        }
+    }
+
+    let err = serde_graphql_input::to_string(&FloatMap).unwrap_err();
+
+    assert_eq!("key must be a GraphQL name, found float", err.to_string());
+}
+
+#[test]
+fn non_enum_one_of_values_are_errors() {
+    let err = "oneOf input must be an enum, found";
+
+    assert_eq!(format!("{err} boolean"), one_of_error(true));
+    assert_eq!(format!("{err} integer"), one_of_error(1_i8));
+    assert_eq!(format!("{err} integer"), one_of_error(1_i16));
+    assert_eq!(format!("{err} integer"), one_of_error(1_i32));
+    assert_eq!(format!("{err} integer"), one_of_error(1_i64));
+    assert_eq!(format!("{err} i128"), one_of_error(1_i128));
+    assert_eq!(format!("{err} integer"), one_of_error(1_u8));
+    assert_eq!(format!("{err} integer"), one_of_error(1_u16));
+    assert_eq!(format!("{err} integer"), one_of_error(1_u32));
+    assert_eq!(format!("{err} integer"), one_of_error(1_u64));
+    assert_eq!(format!("{err} u128"), one_of_error(1_u128));
+    assert_eq!(format!("{err} float"), one_of_error(1.5_f32));
+    assert_eq!(format!("{err} float"), one_of_error(1.5_f64));
+    assert_eq!(format!("{err} char"), one_of_error('a'));
+    assert_eq!(format!("{err} string"), one_of_error("a"));
+    assert_eq!(
+        format!("{err} bytes"),
+        one_of_error(serde_bytes::ByteBuf::from(vec![1]))
+    );
+    assert_eq!(format!("{err} none"), one_of_error(Option::<Enum>::None));
+    assert_eq!(
+        format!("{err} option"),
+        one_of_error(Some(Enum::Newtype(1)))
+    );
+    assert_eq!(format!("{err} unit"), one_of_error(()));
+    assert_eq!(format!("{err} unit struct"), one_of_error(UnitStruct));
+    assert_eq!(format!("{err} sequence"), one_of_error(vec![1]));
+    assert_eq!(format!("{err} tuple"), one_of_error((1, 2)));
+    assert_eq!(
+        format!("{err} tuple struct"),
+        one_of_error(TupleStruct(1, 2))
+    );
+    assert_eq!(format!("{err} map"), one_of_error(BTreeMap::from([(1, 2)])));
+    assert_eq!(format!("{err} struct"), one_of_error(Struct { a: 1 }));
+    assert_eq!(
+        format!("{err} sequence"),
+        one_of_error(NewtypeStruct(vec![1]))
+    );
+}
+
+#[test]
+fn unsupported_one_of_variants_are_errors() {
+    assert_eq!(
+        "oneOf variant `Unit` must be a newtype variant, found 0 fields",
+        one_of_error(Enum::Unit)
+    );
+    assert_eq!(
+        "oneOf variant `Tuple` must be a newtype variant, found 2 fields",
+        one_of_error(Enum::Tuple(1, 2))
+    );
+    assert_eq!(
+        "oneOf variant `Struct` must be a newtype variant, found 1 field",
+        one_of_error(Enum::Struct { a: 1 })
+    );
+}
+
+#[test]
+fn out_of_range_ints_are_errors() {
+    #[derive(Serialize)]
+    struct Input {
+        a: i128,
+    }
+
+    let err = serde_graphql_input::to_string(&Input { a: 1 << 31 }).unwrap_err();
+    assert_eq!(
+        "at a: `2147483648` is out of range for a GraphQL Int",
+        err.to_string()
+    );
+
+    let err = serde_graphql_input::to_string(&u128::MAX).unwrap_err();
+    assert_eq!(
+        "`340282366920938463463374607431768211455` is out of range for a GraphQL Int",
+        err.to_string()
+    );
+}
+
+#[test]
+fn classifies_errors() {
+    use serde_graphql_input::Category;
+
+    let err = serde_graphql_input::to_string(&u64::MAX).unwrap_err();
+    assert_eq!(Category::Data, err.classify());
+    assert!(err.is_data());
+    assert_eq!(None, err.io_error_kind());
+
+    let err = serde_graphql_input::from_str::<Vec<u8>>("[1 2 !]").unwrap_err();
+    assert_eq!(Category::Syntax, err.classify());
+    assert!(err.is_syntax());
+
+    let err = serde_graphql_input::from_str::<Vec<u8>>("[1,2").unwrap_err();
+    assert_eq!(Category::Eof, err.classify());
+    assert!(err.is_eof());
+
+    let err = serde_graphql_input::from_str::<u8>("\"a\"").unwrap_err();
+    assert_eq!(Category::Data, err.classify());
+}
+
+#[test]
+fn exposes_io_errors() {
+    use std::error::Error as _;
+
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = serde_graphql_input::to_writer(FailingWriter, &[1]).unwrap_err();
+    assert!(err.is_io());
+    assert_eq!(Some(std::io::ErrorKind::BrokenPipe), err.io_error_kind());
+    assert_eq!(
+        Some(std::io::ErrorKind::BrokenPipe),
+        err.source()
+            .and_then(|source| source.downcast_ref::<std::io::Error>())
+            .map(std::io::Error::kind)
+    );
+
+    let err: std::io::Error = err.into();
+    assert_eq!(std::io::ErrorKind::BrokenPipe, err.kind());
+}
+
+#[test]
+fn converts_into_io_errors() {
+    let err: std::io::Error = serde_graphql_input::to_string(&u64::MAX)
+        .unwrap_err()
+        .into();
+    assert_eq!(std::io::ErrorKind::InvalidData, err.kind());
+    assert_eq!(
+        "`18446744073709551615` is out of range for a GraphQL Int",
+        err.to_string()
+    );
+
+    let err: std::io::Error = serde_graphql_input::from_str::<Vec<u8>>("[")
+        .unwrap_err()
+        .into();
+    assert_eq!(std::io::ErrorKind::UnexpectedEof, err.kind());
+}
@@ -0,0 +1,85 @@
+use serde::Serialize;
+use serde_graphql_input::FloatPolicy;
+
+mod common;
+
+#[derive(Serialize, Clone, Debug)]
+struct Input {
+    value: f64,
+}
+
+#[test]
+fn writes_whole_floats_with_a_decimal_point() {
+    assert_eq!("1.0", serde_graphql_input::to_string(&1.0_f64).unwrap());
+    assert_eq!("-0.0", serde_graphql_input::to_string(&-0.0_f64).unwrap());
+    assert_eq!("1.0", serde_graphql_input::to_string(&1.0_f32).unwrap());
+    assert_eq!("1e16", serde_graphql_input::to_string(&1e16_f64).unwrap());
+}
+
+#[test]
+fn writes_shortest_round_trip_floats() {
+    assert_eq!("0.1", serde_graphql_input::to_string(&0.1_f64).unwrap());
+    assert_eq!("0.1", serde_graphql_input::to_string(&0.1_f32).unwrap());
+    assert_eq!(
+        "0.30000000000000004",
+        serde_graphql_input::to_string(&(0.1_f64 + 0.2_f64)).unwrap()
+    );
+    assert_eq!(
+        "1.7976931348623157e308",
+        serde_graphql_input::to_string(&f64::MAX).unwrap()
+    );
+    assert_eq!(
+        "5e-324",
+        serde_graphql_input::to_string(&5e-324_f64).unwrap()
+    );
+
+    for value in [0.1, 1.0 / 3.0, 123.456e-7, f64::MIN_POSITIVE, f64::MAX] {
+        let output = serde_graphql_input::to_string(&value).unwrap();
+        let roundtrip: f64 = serde_graphql_input::from_str(&output).unwrap();
+        assert_eq!(value, roundtrip);
+    }
+}
+
+#[test]
+fn rejects_non_finite_floats_by_default() {
+    let err = serde_graphql_input::to_string(&Input { value: f64::NAN }).unwrap_err();
+    assert_eq!("at value: `NaN` is not a finite float", err.to_string());
+    assert!(err.is_data());
+
+    let err = serde_graphql_input::to_string(&f32::INFINITY).unwrap_err();
+    assert_eq!("`inf` is not a finite float", err.to_string());
+
+    let err = serde_graphql_input::to_string(&f64::NEG_INFINITY).unwrap_err();
+    assert_eq!("`-inf` is not a finite float", err.to_string());
+}
+
+#[test]
+fn can_write_non_finite_floats_as_null() {
+    let output = common::to_string_with(&[f64::NAN, f64::INFINITY, 1.5], |ser| {
+        ser.with_float_policy(FloatPolicy::Null)
+    })
+    .unwrap();
+    assert_eq!("[null,null,1.5]", output.as_str());
+
+    let output = common::to_string_with(&f32::NEG_INFINITY, |ser| {
+        ser.with_float_policy(FloatPolicy::Null)
+    })
+    .unwrap();
+    assert_eq!("null", output.as_str());
+}
+
+#[test]
+fn can_write_non_finite_floats_as_strings() {
+    let output =
+        common::to_string_with(&[f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 1.5], |ser| {
+            ser.with_float_policy(FloatPolicy::String)
+        })
+        .unwrap();
+    assert_eq!(r#"["NaN","Infinity","-Infinity",1.5]"#, output.as_str());
+
+    let output = common::to_string_with(&Input { value: 2.0 }, |ser| {
+        ser.with_float_policy(FloatPolicy::String)
+    })
+    .unwrap();
+    assert_eq!("{value:2.0}", output.as_str());
+}
@@ -0,0 +1,105 @@
+use serde::Serialize;
+use serde_graphql_input::IntPolicy;
+
+mod common;
+
+#[test]
+fn writes_ints_within_range() {
+    assert_eq!(
+        "[-2147483648,2147483647]",
+        serde_graphql_input::to_string(&[i64::from(i32::MIN), i64::from(i32::MAX)]).unwrap()
+    );
+    assert_eq!(
+        "[-2147483648,2147483647]",
+        serde_graphql_input::to_string(&[i128::from(i32::MIN), i128::from(i32::MAX)]).unwrap()
+    );
+    assert_eq!(
+        "[0,2147483647,2147483647,2147483647]",
+        serde_graphql_input::to_string(&(
+            0_u32,
+            i32::MAX as u32,
+            i32::MAX as u64,
+            i32::MAX as u128
+        ))
+        .unwrap()
+    );
+}
+
+fn out_of_range_error<T>(value: T) -> String
+where
+    T: Serialize,
+{
+    let err = serde_graphql_input::to_string(&value).unwrap_err();
+    assert!(err.is_data());
+    err.to_string()
+}
+
+#[test]
+fn rejects_ints_out_of_range_by_default() {
+    let err = "is out of range for a GraphQL Int";
+
+    assert_eq!(
+        format!("`2147483648` {err}"),
+        out_of_range_error(i32::MAX as u32 + 1)
+    );
+    assert_eq!(
+        format!("`-2147483649` {err}"),
+        out_of_range_error(i64::from(i32::MIN) - 1)
+    );
+    assert_eq!(
+        format!("`18446744073709551615` {err}"),
+        out_of_range_error(u64::MAX)
+    );
+    assert_eq!(
+        format!("`-9223372036854775808` {err}"),
+        out_of_range_error(i64::MIN)
+    );
+    assert_eq!(
+        format!("`-170141183460469231731687303715884105728` {err}"),
+        out_of_range_error(i128::MIN)
+    );
+    assert_eq!(
+        format!("`340282366920938463463374607431768211455` {err}"),
+        out_of_range_error(u128::MAX)
+    );
+}
+
+#[test]
+fn can_pass_through_ints_out_of_range() {
+    let output =
+        common::to_string_with(&(u64::MAX, i64::MIN, i128::MIN, u128::MAX, 1_u32), |ser| {
+            ser.with_int_policy(IntPolicy::PassThrough)
+        })
+        .unwrap();
+
+    assert_eq!(
+        "[18446744073709551615,-9223372036854775808,-170141183460469231731687303715884105728,340282366920938463463374607431768211455,1]",
+        output.as_str()
+    );
+}
+
+#[test]
+fn can_stringify_ints_out_of_range() {
+    #[derive(Serialize)]
+    struct Input {
+        id: u64,
+        small: u64,
+    }
+
+    let input = Input {
+        id: 9007199254740993,
+        small: 1,
+    };
+    let output =
+        common::to_string_with(&input, |ser| ser.with_int_policy(IntPolicy::String)).unwrap();
+    assert_eq!(r#"{id:"9007199254740993",small:1}"#, output.as_str());
+
+    let output = common::to_string_with(&(i128::MIN, u128::MAX), |ser| {
+        ser.with_int_policy(IntPolicy::String)
+    })
+    .unwrap();
+    assert_eq!(
+        r#"["-170141183460469231731687303715884105728","340282366920938463463374607431768211455"]"#,
+        output.as_str()
+    );
+}
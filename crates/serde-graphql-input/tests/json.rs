@@ -0,0 +1,177 @@
+#![cfg(feature = "json")]
+
+use serde_graphql_input::{PathSegment, PrettyFormatter, RawGraphQL, Serializer, Value};
+
+#[test]
+fn converts_json_text() {
+    let output = serde_graphql_input::from_json_str(
+        r#"{"id": 1, "name": "a", "ratio": 0.5, "tags": ["x", null], "nested": {"active": true}}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        r#"{id:1,name:"a",ratio:0.5,tags:["x",null],nested:{active:true}}"#,
+        output
+    );
+}
+
+#[test]
+fn converts_empty_containers() {
+    let output = serde_graphql_input::from_json_str(r#"{"a": [], "b": {}}"#).unwrap();
+
+    assert_eq!("{a:[],b:{}}", output);
+}
+
+#[test]
+fn resolves_enum_values() {
+    let output = serde_graphql_input::from_json_str_with(
+        r#"{"status": "ACTIVE", "name": "ACTIVE", "roles": ["ADMIN"]}"#,
+        |path, _| match path {
+            [PathSegment::Field(field)] => field == "status",
+            [PathSegment::Field(field), PathSegment::Index(_)] => field == "roles",
+            _ => false,
+        },
+    )
+    .unwrap();
+
+    assert_eq!(r#"{status:ACTIVE,name:"ACTIVE",roles:[ADMIN]}"#, output);
+}
+
+#[test]
+fn rejects_resolved_strings_that_are_not_enum_values() {
+    for name in ["true", "false", "null"] {
+        let json = format!(r#"{{"a": "{name}"}}"#);
+
+        let err = serde_graphql_input::from_json_str_with(&json, |_, _| true).unwrap_err();
+        assert_eq!(
+            format!("at a: `{name}` is not a valid GraphQL enum value"),
+            err.to_string()
+        );
+        assert!(err.is_data());
+
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let err = Value::from_json_with(json, |_, _| true).unwrap_err();
+        assert_eq!(
+            format!("at a: `{name}` is not a valid GraphQL enum value"),
+            err.to_string()
+        );
+    }
+
+    let json = serde_json::json!({"a": "not-a-name"});
+    let err = Value::from_json_with(json, |_, _| true).unwrap_err();
+    assert_eq!(
+        "at a: `not-a-name` is not a valid GraphQL name",
+        err.to_string()
+    );
+}
+
+#[test]
+fn rejects_keys_that_are_not_names() {
+    let err = serde_graphql_input::from_json_str(r#"{"a": {"not-a-name": 1}}"#).unwrap_err();
+
+    assert_eq!(
+        "at a: `not-a-name` is not a valid GraphQL name",
+        err.to_string()
+    );
+    assert!(err.is_data());
+}
+
+#[test]
+fn reports_the_path_of_invalid_values() {
+    let err = serde_graphql_input::from_json_str(r#"{"items": [1, 4294967296]}"#).unwrap_err();
+
+    assert_eq!(
+        "at items[1]: `4294967296` is out of range for a GraphQL Int",
+        err.to_string()
+    );
+}
+
+#[test]
+fn reports_json_syntax_errors() {
+    let err = serde_graphql_input::from_json_str(r#"{"a": }"#).unwrap_err();
+    assert!(err.is_syntax());
+    assert_eq!("expected value at line 1 column 7", err.to_string());
+
+    let err = serde_graphql_input::from_json_str(r#"{"a": 1"#).unwrap_err();
+    assert!(err.is_eof());
+}
+
+#[test]
+fn can_transcode_with_a_formatter() {
+    let mut writer = Vec::new();
+    let mut ser = Serializer::with_formatter(&mut writer, PrettyFormatter::new());
+    serde_graphql_input::transcode_json(&mut ser, r#"{"a": [1, 2]}"#, |_, _| false).unwrap();
+
+    assert_eq!(
+        "{\n  a: [\n    1,\n    2\n  ]\n}",
+        String::from_utf8(writer).unwrap()
+    );
+}
+
+#[test]
+fn converts_json_values() {
+    let json = serde_json::json!({"id": 1, "status": "ACTIVE", "tags": ["a"], "ratio": 1.5});
+
+    let value = Value::try_from(json.clone()).unwrap();
+    assert_eq!(
+        r#"{id:1,ratio:1.5,status:"ACTIVE",tags:["a"]}"#,
+        value.to_string()
+    );
+
+    let value = Value::from_json_with(
+        json,
+        |path, _| matches!(path, [PathSegment::Field(field)] if field == "status"),
+    )
+    .unwrap();
+    assert_eq!(
+        r#"{id:1,ratio:1.5,status:ACTIVE,tags:["a"]}"#,
+        value.to_string()
+    );
+}
+
+#[test]
+fn rejects_json_integers_beyond_i64() {
+    let json = serde_json::json!({"ids": [1, u64::MAX], "max": i64::MAX, "min": i64::MIN});
+
+    let err = Value::try_from(json).unwrap_err();
+    assert_eq!(
+        "at ids[1]: `18446744073709551615` is out of range for a GraphQL Int",
+        err.to_string()
+    );
+
+    let value = Value::try_from(serde_json::json!([i64::MAX, i64::MIN, 1e20])).unwrap();
+    assert_eq!(
+        Value::List(vec![
+            Value::Int(i64::MAX),
+            Value::Int(i64::MIN),
+            Value::Float(1e20)
+        ]),
+        value
+    );
+}
+
+#[test]
+fn converts_values_to_json() {
+    let value: Value =
+        serde_graphql_input::from_str(r#"{id:1,status:ACTIVE,after:$cursor,items:[1.5,null,"a"]}"#)
+            .unwrap();
+
+    assert_eq!(
+        serde_json::json!({
+            "id": 1,
+            "status": "ACTIVE",
+            "after": "cursor",
+            "items": [1.5, null, "a"]
+        }),
+        serde_json::Value::from(value)
+    );
+}
+
+#[test]
+fn checks_raw_fragments_read_from_json() {
+    let raw: RawGraphQL = serde_json::from_str(r#""{a: [1, 2]}""#).unwrap();
+    assert_eq!("{a: [1, 2]}", raw.get());
+
+    let err = serde_json::from_str::<RawGraphQL>(r#""1) { hacked } #""#).unwrap_err();
+    assert_eq!("trailing characters at line 1 column 2", err.to_string());
+}
@@ -0,0 +1,75 @@
+use serde::Serialize;
+use serde_graphql_input::{graphql_input, Map, Value, Variable};
+
+#[test]
+fn builds_scalars() {
+    assert_eq!(Value::Null, graphql_input!(null));
+    assert_eq!(Value::Boolean(true), graphql_input!(true));
+    assert_eq!(Value::Boolean(false), graphql_input!(false));
+    assert_eq!(Value::Int(10), graphql_input!(10));
+    assert_eq!(Value::Int(-3), graphql_input!(-3));
+    assert_eq!(Value::Float(1.5), graphql_input!(1.5));
+    assert_eq!(Value::String("a".into()), graphql_input!("a"));
+    assert_eq!(Value::Enum("ACTIVE".into()), graphql_input!(ACTIVE));
+    assert_eq!(Value::Variable("id".into()), graphql_input!($id));
+    assert_eq!(Value::List(vec![]), graphql_input!([]));
+    assert_eq!(Value::Object(Map::new()), graphql_input!({}));
+}
+
+#[test]
+fn builds_nested_values() {
+    let value = graphql_input!({
+        id: $id,
+        filter: { status: ACTIVE, tags: ["a", "b"] },
+        limit: 10
+    });
+
+    assert_eq!(
+        r#"{id:$id,filter:{status:ACTIVE,tags:["a","b"]},limit:10}"#,
+        value.to_string()
+    );
+}
+
+#[test]
+fn accepts_trailing_commas() {
+    let value = graphql_input!({
+        list: [1, 2,],
+        empty: [],
+    });
+
+    assert_eq!("{list:[1,2],empty:[]}", value.to_string());
+}
+
+#[test]
+fn interpolates_expressions() {
+    #[derive(Serialize)]
+    struct Filter {
+        name: &'static str,
+    }
+
+    let limit = 5;
+    let names = vec!["x", "y"];
+    let value = graphql_input!({
+        limit: (limit * 2),
+        names: (names),
+        filter: (Filter { name: "a" }),
+        after: (Variable("cursor")),
+        first: [(limit), $first, DESC, null],
+    });
+
+    assert_eq!(
+        r#"{limit:10,names:["x","y"],filter:{name:"a"},after:$cursor,first:[5,$first,DESC,null]}"#,
+        value.to_string()
+    );
+}
+
+#[test]
+fn accepts_quoted_and_computed_keys() {
+    let key = "computed";
+    let value = graphql_input!({
+        "quoted": 1,
+        (key): 2,
+    });
+
+    assert_eq!("{quoted:1,computed:2}", value.to_string());
+}
@@ -0,0 +1,100 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+#[test]
+fn can_serialize_bool_keys() {
+    let input = BTreeMap::from([(false, 0), (true, 1)]);
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!("{false:0,true:1}", output.as_str())
+}
+
+#[test]
+fn can_serialize_char_keys() {
+    let input = BTreeMap::from([('a', 0), ('_', 1)]);
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!("{_:1,a:0}", output.as_str())
+}
+
+#[test]
+fn can_serialize_unit_variant_keys() {
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Key {
+        ItemA,
+        ItemB,
+    }
+
+    let input = BTreeMap::from([(Key::ItemA, 0), (Key::ItemB, 1)]);
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    assert_eq!("{ItemA:0,ItemB:1}", output.as_str());
+
+    let roundtrip: BTreeMap<Key, u8> = serde_graphql_input::from_str(&output).unwrap();
+    assert_eq!(input, roundtrip);
+}
+
+#[test]
+fn can_serialize_newtype_struct_keys() {
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+    struct Key(String);
+
+    let input = HashMap::from([(Key("something".into()), 1)]);
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    assert_eq!("{something:1}", output.as_str());
+
+    let roundtrip: HashMap<Key, u8> = serde_graphql_input::from_str(&output).unwrap();
+    assert_eq!(input, roundtrip);
+}
+
+#[test]
+fn can_roundtrip_bool_keys() {
+    let input = BTreeMap::from([(false, 0), (true, 1)]);
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    let roundtrip: BTreeMap<bool, u8> = serde_graphql_input::from_str(&output).unwrap();
+
+    assert_eq!(input, roundtrip)
+}
+
+#[test]
+fn rejects_keys_that_are_not_names() {
+    let err = serde_graphql_input::to_string(&HashMap::from([(1_u32, 0)])).unwrap_err();
+    assert_eq!("`1` is not a valid GraphQL name", err.to_string());
+
+    let err = serde_graphql_input::to_string(&HashMap::from([(-1_i64, 0)])).unwrap_err();
+    assert_eq!("`-1` is not a valid GraphQL name", err.to_string());
+
+    let err = serde_graphql_input::to_string(&HashMap::from([('1', 0)])).unwrap_err();
+    assert_eq!("`1` is not a valid GraphQL name", err.to_string());
+
+    let err = serde_graphql_input::to_string(&HashMap::from([("some key", 0)])).unwrap_err();
+    assert_eq!("`some key` is not a valid GraphQL name", err.to_string());
+}
+
+#[test]
+fn rejects_keys_that_can_never_be_names() {
+    #[derive(Serialize, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    enum Key {
+        Newtype(u8),
+    }
+
+    let err = serde_graphql_input::to_string(&BTreeMap::from([(Key::Newtype(1), 0)])).unwrap_err();
+    assert_eq!(
+        "key must be a GraphQL name, found newtype variant",
+        err.to_string()
+    );
+
+    let err = serde_graphql_input::to_string(&BTreeMap::from([((1, 2), 0)])).unwrap_err();
+    assert_eq!("key must be a GraphQL name, found tuple", err.to_string());
+
+    let err = serde_graphql_input::to_string(&BTreeMap::from([(Some("a"), 0)])).unwrap_err();
+    assert_eq!("key must be a GraphQL name, found option", err.to_string());
+
+    let err = serde_graphql_input::to_string(&BTreeMap::from([((), 0)])).unwrap_err();
+    assert_eq!("key must be a GraphQL name, found unit", err.to_string());
+}
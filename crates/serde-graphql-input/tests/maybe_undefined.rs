@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_graphql_input::MaybeUndefined;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Input {
+    #[serde(default)]
+    name: MaybeUndefined<String>,
+    #[serde(default)]
+    age: MaybeUndefined<u8>,
+    #[serde(default)]
+    tags: MaybeUndefined<Vec<String>>,
+}
+
+#[test]
+fn omits_undefined_fields() {
+    let cases = [
+        (
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Undefined,
+            "{}",
+        ),
+        (
+            MaybeUndefined::Value("a".to_string()),
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Undefined,
+            r#"{name:"a"}"#,
+        ),
+        (
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Null,
+            MaybeUndefined::Undefined,
+            "{age:null}",
+        ),
+        (
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Value(vec![]),
+            "{tags:[]}",
+        ),
+        (
+            MaybeUndefined::Null,
+            MaybeUndefined::Undefined,
+            MaybeUndefined::Value(vec!["x".to_string()]),
+            r#"{name:null,tags:["x"]}"#,
+        ),
+        (
+            MaybeUndefined::Value("a".to_string()),
+            MaybeUndefined::Value(3),
+            MaybeUndefined::Null,
+            r#"{name:"a",age:3,tags:null}"#,
+        ),
+    ];
+
+    for (name, age, tags, expected) in cases {
+        let input = Input { name, age, tags };
+
+        let output = serde_graphql_input::to_string(&input).unwrap();
+        assert_eq!(expected, output.as_str());
+
+        let roundtrip: Input = serde_graphql_input::from_str(&output).unwrap();
+        assert_eq!(input, roundtrip);
+    }
+}
+
+#[test]
+fn omits_undefined_fields_when_pretty_printing() {
+    let input = Input {
+        name: MaybeUndefined::Undefined,
+        age: MaybeUndefined::Value(3),
+        tags: MaybeUndefined::Undefined,
+    };
+    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    assert_eq!("{\n  age: 3\n}", output.as_str());
+
+    let input = Input {
+        name: MaybeUndefined::Undefined,
+        age: MaybeUndefined::Undefined,
+        tags: MaybeUndefined::Undefined,
+    };
+    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    assert_eq!("{}", output.as_str());
+}
+
+#[test]
+fn omits_undefined_map_entries_and_variant_fields() {
+    #[derive(Serialize)]
+    enum Update {
+        Set {
+            value: MaybeUndefined<u8>,
+            reason: MaybeUndefined<String>,
+        },
+    }
+
+    #[derive(Serialize)]
+    struct Wrapper(MaybeUndefined<u8>);
+
+    let output = serde_graphql_input::to_string(&BTreeMap::from([
+        ("a", MaybeUndefined::Undefined),
+        ("b", MaybeUndefined::Value(1)),
+        ("c", MaybeUndefined::Undefined),
+    ]))
+    .unwrap();
+    assert_eq!("{b:1}", output.as_str());
+
+    let output = serde_graphql_input::to_string(&Update::Set {
+        value: MaybeUndefined::Undefined,
+        reason: MaybeUndefined::Null,
+    })
+    .unwrap();
+    assert_eq!("{Set:{reason:null}}", output.as_str());
+
+    let output = serde_graphql_input::to_string(&BTreeMap::from([
+        ("a", Wrapper(MaybeUndefined::Undefined)),
+        ("b", Wrapper(MaybeUndefined::Null)),
+    ]))
+    .unwrap();
+    assert_eq!("{b:null}", output.as_str());
+}
+
+#[test]
+fn writes_undefined_as_null_outside_of_objects() {
+    let output = serde_graphql_input::to_string(&vec![
+        MaybeUndefined::Undefined,
+        MaybeUndefined::Null,
+        MaybeUndefined::Value(1),
+    ])
+    .unwrap();
+    assert_eq!("[null,null,1]", output.as_str());
+
+    let output = serde_graphql_input::to_string(&MaybeUndefined::<u8>::Undefined).unwrap();
+    assert_eq!("null", output.as_str());
+}
+
+#[test]
+fn reads_missing_fields_as_undefined() {
+    let output: Input = serde_graphql_input::from_str(r#"{age:null}"#).unwrap();
+
+    assert_eq!(
+        Input {
+            name: MaybeUndefined::Undefined,
+            age: MaybeUndefined::Null,
+            tags: MaybeUndefined::Undefined,
+        },
+        output
+    );
+}
+
+#[test]
+fn converts_to_options() {
+    assert_eq!(None, MaybeUndefined::<u8>::Undefined.into_option());
+    assert_eq!(Some(None), MaybeUndefined::<u8>::Null.into_option());
+    assert_eq!(Some(Some(1)), MaybeUndefined::Value(1).into_option());
+
+    assert_eq!(MaybeUndefined::Null, MaybeUndefined::<u8>::from(None));
+    assert_eq!(MaybeUndefined::Value(1), MaybeUndefined::from(Some(1)));
+}
+
+#[test]
+fn checks_each_field_once_before_writing_it() {
+    use std::cell::Cell;
+
+    struct Counted<'a>(&'a Cell<usize>);
+
+    impl Serialize for Counted<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            self.0.set(self.0.get() + 1);
+            serializer.serialize_u32(1)
+        }
+    }
+
+    #[derive(Serialize)]
+    struct Input<'a> {
+        value: Counted<'a>,
+    }
+
+    let calls = Cell::new(0);
+    let input = Input {
+        value: Counted(&calls),
+    };
+
+    assert_eq!("{value:1}", serde_graphql_input::to_string(&input).unwrap());
+    assert_eq!(2, calls.replace(0));
+
+    assert_eq!(
+        "(value:1)",
+        serde_graphql_input::to_arguments_string(&input).unwrap()
+    );
+    assert_eq!(2, calls.get());
+}
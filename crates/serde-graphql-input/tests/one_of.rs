@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+use serde_graphql_input::OneOf;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+enum Lookup {
+    ById(String),
+    ByEmail(String),
+    Position(u32),
+}
+
+#[test]
+fn can_serialize_one_of_newtype_variants() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        user: OneOf<Lookup>,
+        others: Vec<OneOf<Lookup>>,
+    }
+
+    let input = Input {
+        user: OneOf(Lookup::ById("1".into())),
+        others: vec![
+            OneOf(Lookup::ByEmail("a@b.c".into())),
+            OneOf(Lookup::Position(2)),
+        ],
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(
+        r#"{user:{byId:"1"},others:[{byEmail:"a@b.c"},{position:2}]}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_serialize_optional_one_of() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        user: Option<OneOf<Lookup>>,
+    }
+
+    let input = Input {
+        user: Some(Lookup::Position(1).into()),
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(r#"{user:{position:1}}"#, output.as_str())
+}
+
+#[test]
+fn rejects_variants_without_exactly_one_value() {
+    #[derive(Serialize, Clone, Debug)]
+    enum Invalid {
+        Unit,
+        Tuple(u8, u8),
+        Struct { a: u8 },
+    }
+
+    let err = serde_graphql_input::to_string(&OneOf(Invalid::Unit)).unwrap_err();
+    assert_eq!(
+        "oneOf variant `Unit` must be a newtype variant, found 0 fields",
+        err.to_string()
+    );
+
+    let err = serde_graphql_input::to_string(&OneOf(Invalid::Tuple(1, 2))).unwrap_err();
+    assert_eq!(
+        "oneOf variant `Tuple` must be a newtype variant, found 2 fields",
+        err.to_string()
+    );
+
+    let err = serde_graphql_input::to_string(&OneOf(Invalid::Struct { a: 1 })).unwrap_err();
+    assert_eq!(
+        "oneOf variant `Struct` must be a newtype variant, found 1 field",
+        err.to_string()
+    );
+}
+
+#[test]
+fn rejects_non_enum_values() {
+    let err = serde_graphql_input::to_string(&OneOf("something")).unwrap_err();
+
+    assert_eq!("oneOf input must be an enum, found string", err.to_string())
+}
+
+#[test]
+fn can_roundtrip_one_of() {
+    let input = vec![
+        OneOf(Lookup::ById("1".into())),
+        OneOf(Lookup::ByEmail("a@b.c".into())),
+    ];
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    let roundtrip: Vec<OneOf<Lookup>> = serde_graphql_input::from_str(&output).unwrap();
+
+    assert_eq!(input, roundtrip);
+
+    let err = serde_graphql_input::from_str::<OneOf<Lookup>>(r#"{byName:"x"}"#).unwrap_err();
+    assert_eq!(
+        "unknown variant `byName`, expected one of `ById`, `ByEmail`, `Position` at line 1 column 7",
+        err.to_string()
+    );
+}
+
+#[test]
+fn camel_cases_acronyms_in_variant_names() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Source {
+        HTTPUrl(String),
+        SHA256(String),
+        ByIDAndName(String),
+    }
+
+    let input = vec![
+        OneOf(Source::HTTPUrl("a".into())),
+        OneOf(Source::SHA256("b".into())),
+        OneOf(Source::ByIDAndName("c".into())),
+    ];
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    assert_eq!(r#"[{httpUrl:"a"},{sha256:"b"},{byIdAndName:"c"}]"#, output);
+
+    let roundtrip: Vec<OneOf<Source>> = serde_graphql_input::from_str(&output).unwrap();
+    assert_eq!(input, roundtrip);
+}
@@ -0,0 +1,104 @@
+use std::io::Write;
+
+use serde::Serialize;
+use serde_graphql_input::{CompactFormatter, PrettyFormatter};
+
+#[derive(Serialize, Clone, Debug)]
+struct Input {
+    items: Vec<u8>,
+}
+
+#[test]
+fn can_write_compact_to_all_outputs() {
+    let input = Input { items: vec![1, 2] };
+
+    let string = serde_graphql_input::to_string(&input).unwrap();
+    let vec = serde_graphql_input::to_vec(&input).unwrap();
+    let mut writer = Vec::new();
+    serde_graphql_input::to_writer(&mut writer, &input).unwrap();
+
+    assert_eq!("{items:[1,2]}", string.as_str());
+    assert_eq!(b"{items:[1,2]}", vec.as_slice());
+    assert_eq!(b"{items:[1,2]}", writer.as_slice());
+}
+
+#[test]
+fn can_write_pretty_to_all_outputs() {
+    let input = Input { items: vec![1, 2] };
+    let expected = "{\n  items: [\n    1,\n    2\n  ]\n}";
+
+    let string = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let vec = serde_graphql_input::to_vec_pretty(&input).unwrap();
+    let mut writer = Vec::new();
+    serde_graphql_input::to_writer_pretty(&mut writer, &input).unwrap();
+
+    assert_eq!(expected, string.as_str());
+    assert_eq!(expected.as_bytes(), vec.as_slice());
+    assert_eq!(expected.as_bytes(), writer.as_slice());
+}
+
+#[test]
+fn can_write_with_formatter() {
+    let input = Input { items: vec![1, 2] };
+
+    let compact = serde_graphql_input::to_string_with_formatter(CompactFormatter, &input).unwrap();
+    let pretty =
+        serde_graphql_input::to_string_with_formatter(PrettyFormatter::with_indent(b"\t"), &input)
+            .unwrap();
+
+    assert_eq!("{items:[1,2]}", compact.as_str());
+    assert_eq!("{\n\titems: [\n\t\t1,\n\t\t2\n\t]\n}", pretty.as_str());
+}
+
+#[test]
+fn appends_to_existing_writer() {
+    let input = Input { items: vec![1] };
+
+    let mut body = Vec::new();
+    write!(body, "mutation {{ create(input: ").unwrap();
+    serde_graphql_input::to_writer(&mut body, &input).unwrap();
+    write!(body, ") }}").unwrap();
+
+    assert_eq!(
+        "mutation { create(input: {items:[1]}) }",
+        String::from_utf8(body).unwrap()
+    );
+}
+
+#[test]
+fn surfaces_writer_errors() {
+    struct FailingWriter;
+
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("broken pipe"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let err = serde_graphql_input::to_writer(FailingWriter, &Input { items: vec![] }).unwrap_err();
+
+    assert_eq!("broken pipe", err.to_string());
+}
+
+#[test]
+fn rejects_formatters_writing_invalid_utf8() {
+    struct InvalidFormatter;
+
+    impl serde_graphql_input::Formatter for InvalidFormatter {
+        fn write_null<W>(&mut self, writer: &mut W) -> std::io::Result<()>
+        where
+            W: ?Sized + Write,
+        {
+            writer.write_all(b"\xff")
+        }
+    }
+
+    let err = serde_graphql_input::to_string_with_formatter(InvalidFormatter, &()).unwrap_err();
+
+    assert!(err.is_io());
+    assert_eq!(Some(std::io::ErrorKind::InvalidData), err.io_error_kind());
+}
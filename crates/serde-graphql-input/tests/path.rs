@@ -0,0 +1,98 @@
+use std::collections::BTreeMap;
+
+use serde::{Serialize, Serializer};
+use serde_graphql_input::{OneOf, PathSegment};
+
+#[derive(Clone, Debug)]
+struct Id(&'static str);
+
+impl Serialize for Id {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if self.0.is_empty() {
+            return Err(serde::ser::Error::custom("invalid ID"));
+        }
+        serializer.serialize_str(self.0)
+    }
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct Item {
+    owner: Id,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct Input {
+    items: Vec<Item>,
+}
+
+#[test]
+fn reports_path_to_failing_field() {
+    let input = Input {
+        items: vec![
+            Item { owner: Id("a") },
+            Item { owner: Id("b") },
+            Item { owner: Id("c") },
+            Item { owner: Id("") },
+        ],
+    };
+
+    let err = serde_graphql_input::to_string(&input).unwrap_err();
+
+    assert_eq!("at items[3].owner: invalid ID", err.to_string());
+    assert_eq!(
+        &[
+            PathSegment::Field("items".into()),
+            PathSegment::Index(3),
+            PathSegment::Field("owner".into()),
+        ],
+        err.path()
+    );
+}
+
+#[test]
+fn reports_path_through_maps_and_variants() {
+    #[derive(Serialize, Clone, Debug)]
+    enum Lookup {
+        ById(Id),
+        Pair(Id, Id),
+        Named { id: Id },
+    }
+
+    let err = serde_graphql_input::to_string(&BTreeMap::from([("input", Lookup::ById(Id("")))]))
+        .unwrap_err();
+    assert_eq!("at input.ById: invalid ID", err.to_string());
+
+    let err = serde_graphql_input::to_string(&vec![Lookup::Pair(Id("a"), Id(""))]).unwrap_err();
+    assert_eq!("at [0].Pair[1]: invalid ID", err.to_string());
+
+    let err = serde_graphql_input::to_string(&Lookup::Named { id: Id("") }).unwrap_err();
+    assert_eq!("at Named.id: invalid ID", err.to_string());
+
+    let err = serde_graphql_input::to_string(&OneOf(Lookup::ById(Id("")))).unwrap_err();
+    assert_eq!("at byId: invalid ID", err.to_string());
+}
+
+#[test]
+fn reports_path_to_invalid_key() {
+    let err = serde_graphql_input::to_string(&BTreeMap::from([(
+        "outer",
+        BTreeMap::from([("not a name", 1)]),
+    )]))
+    .unwrap_err();
+
+    assert_eq!(
+        "at outer: `not a name` is not a valid GraphQL name",
+        err.to_string()
+    );
+}
+
+#[test]
+fn root_errors_have_no_path() {
+    let err = serde_graphql_input::to_string(&Id("")).unwrap_err();
+
+    assert_eq!("invalid ID", err.to_string());
+    assert!(err.path().is_empty());
+}
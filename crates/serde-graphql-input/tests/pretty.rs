@@ -0,0 +1,100 @@
+use serde::Serialize;
+
+#[test]
+fn can_pretty_print_struct() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        something: String,
+        #[serde(rename = "somethingElse")]
+        something_else: u8,
+    }
+
+    let input = Input {
+        something: "Something".into(),
+        something_else: 1,
+    };
+
+    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+
+    assert_eq!(
+        r#"{
+  something: "Something",
+  somethingElse: 1
+}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_pretty_print_nested_items() {
+    #[derive(Serialize, Clone, Debug)]
+    enum VariantEnum {
+        ItemA,
+        ItemB,
+    }
+
+    #[derive(Serialize, Clone, Debug)]
+    struct Nested {
+        items: Vec<VariantEnum>,
+        empty: Vec<String>,
+    }
+
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        nested: Nested,
+        item: String,
+    }
+
+    let input = Input {
+        nested: Nested {
+            items: vec![VariantEnum::ItemA, VariantEnum::ItemB],
+            empty: vec![],
+        },
+        item: "some item".into(),
+    };
+
+    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+
+    assert_eq!(
+        r#"{
+  nested: {
+    items: [
+      ItemA,
+      ItemB
+    ],
+    empty: []
+  },
+  item: "some item"
+}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_pretty_print_with_custom_indent() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        items: Vec<u8>,
+    }
+
+    let input = Input { items: vec![1, 2] };
+
+    let mut writer = Vec::new();
+    let mut ser = serde_graphql_input::Serializer::with_formatter(
+        &mut writer,
+        serde_graphql_input::PrettyFormatter::with_indent(b"\t"),
+    );
+    input.serialize(&mut ser).unwrap();
+
+    assert_eq!(
+        "{\n\titems: [\n\t\t1,\n\t\t2\n\t]\n}",
+        String::from_utf8(writer).unwrap()
+    )
+}
+
+#[test]
+fn pretty_prints_scalars_as_is() {
+    let output = serde_graphql_input::to_string_pretty(&"something").unwrap();
+
+    assert_eq!(r#""something""#, output.as_str())
+}
@@ -10,7 +10,7 @@ mod integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -24,7 +24,7 @@ mod integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -38,7 +38,7 @@ mod integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -52,7 +52,7 @@ mod integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -70,7 +70,7 @@ mod unsigned_integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -84,7 +84,7 @@ mod unsigned_integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -98,7 +98,7 @@ mod unsigned_integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -112,7 +112,7 @@ mod unsigned_integer {
 
         let input = Input { something: 123 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123}"#, output.as_str())
     }
@@ -130,7 +130,7 @@ mod float {
 
         let input = Input { something: 123.5 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123.5}"#, output.as_str())
     }
@@ -144,7 +144,7 @@ mod float {
 
         let input = Input { something: 123.5 };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
         assert_eq!(r#"{something:123.5}"#, output.as_str())
     }
@@ -162,8 +162,52 @@ mod char {
 
         let input = Input { something: 'a' };
 
-        let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+        let output = serde_graphql_input::to_string(&input).unwrap();
 
-        assert_eq!(r#"{something:'a'}"#, output.as_str())
+        assert_eq!(r#"{something:"a"}"#, output.as_str())
+    }
+    const ESCAPES: &[(char, &str)] = &[
+        ('"', r#""\"""#),
+        ('\\', r#""\\""#),
+        ('/', r#""/""#),
+        ('\x08', r#""\b""#),
+        ('\x0c', r#""\f""#),
+        ('\n', r#""\n""#),
+        ('\r', r#""\r""#),
+        ('\t', r#""\t""#),
+        ('\0', r#""\u0000""#),
+        ('\x0b', r#""\u000b""#),
+        ('\x1f', r#""\u001f""#),
+        ('a', r#""a""#),
+        ('æ', r#""æ""#),
+        ('😀', r#""😀""#),
+    ];
+
+    #[test]
+    fn escapes_chars() {
+        for (value, expected) in ESCAPES {
+            let output = serde_graphql_input::to_string(value).unwrap();
+
+            assert_eq!(*expected, output.as_str(), "escaping {value:?}");
+        }
+    }
+
+    #[test]
+    fn escapes_strs() {
+        for (value, expected) in ESCAPES {
+            let output = serde_graphql_input::to_string(&value.to_string()).unwrap();
+
+            assert_eq!(*expected, output.as_str(), "escaping {value:?}");
+        }
+    }
+
+    #[test]
+    fn roundtrips_escaped_chars() {
+        for (value, _) in ESCAPES {
+            let output = serde_graphql_input::to_string(value).unwrap();
+            let roundtrip: char = serde_graphql_input::from_str(&output).unwrap();
+
+            assert_eq!(*value, roundtrip);
+        }
     }
 }
@@ -0,0 +1,106 @@
+use serde::{Deserialize, Serialize};
+use serde_graphql_input::RawGraphQL;
+
+#[test]
+fn can_embed_raw_fragments() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        name: String,
+        filter: RawGraphQL,
+    }
+
+    let input = Input {
+        name: "something".into(),
+        filter: RawGraphQL::from_string(r#"{status: ACTIVE, owner: $owner, ids: [1, 2]}"#.into())
+            .unwrap(),
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(
+        r#"{name:"something",filter:{status: ACTIVE, owner: $owner, ids: [1, 2]}}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn rejects_malformed_fragments() {
+    let err = RawGraphQL::from_string(r#"{status: ACTIVE"#.into()).unwrap_err();
+    assert_eq!(
+        "EOF while parsing a value at line 1 column 15",
+        err.to_string()
+    );
+
+    let err = RawGraphQL::from_string(r#"1, 2"#.into()).unwrap_err();
+    assert_eq!("trailing characters at line 1 column 2", err.to_string());
+
+    let err = RawGraphQL::from_string(r#""x") { injected }"#.into()).unwrap_err();
+    assert_eq!("trailing characters at line 1 column 4", err.to_string());
+}
+
+#[test]
+fn rejects_comments_and_commas_around_fragments() {
+    let err = RawGraphQL::from_string("1 # c".into()).unwrap_err();
+    assert_eq!("trailing characters at line 1 column 3", err.to_string());
+
+    let err = RawGraphQL::from_string("1,".into()).unwrap_err();
+    assert_eq!("trailing characters at line 1 column 2", err.to_string());
+
+    let err = RawGraphQL::from_string("# c\n1".into()).unwrap_err();
+    assert_eq!("expected value at line 1 column 1", err.to_string());
+
+    let err = RawGraphQL::from_string(",1".into()).unwrap_err();
+    assert_eq!("expected value at line 1 column 1", err.to_string());
+}
+
+#[test]
+fn embeds_fragments_between_fields() {
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Input {
+        a: RawGraphQL,
+        b: u32,
+    }
+
+    let input = Input {
+        a: RawGraphQL::from_string(" [1, # one\n2]\n".into()).unwrap(),
+        b: 2,
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    assert_eq!("{a: [1, # one\n2]\n,b:2}", output);
+
+    let roundtrip: Input = serde_graphql_input::from_str(&output).unwrap();
+    assert_eq!(2, roundtrip.b);
+}
+
+#[test]
+fn writes_unchecked_fragments_verbatim() {
+    let raw = RawGraphQL::from_string_unchecked("ACTIVE".into());
+
+    let output = serde_graphql_input::to_string(&vec![raw]).unwrap();
+
+    assert_eq!("[ACTIVE]", output.as_str())
+}
+
+#[test]
+fn can_roundtrip_raw_fragments() {
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Input {
+        filter: RawGraphQL,
+        after: u8,
+    }
+
+    let input = Input {
+        filter: RawGraphQL::from_string(r#"{tags: ["a" "b"], owner: $owner}"#.into()).unwrap(),
+        after: 1,
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    let roundtrip: Input = serde_graphql_input::from_str(&output).unwrap();
+
+    assert_eq!(input, roundtrip);
+    assert_eq!(
+        r#"{tags: ["a" "b"], owner: $owner}"#,
+        roundtrip.filter.get()
+    );
+}
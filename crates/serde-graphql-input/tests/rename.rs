@@ -0,0 +1,184 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+use serde_graphql_input::{RenamePolicy, Serializer};
+
+mod common;
+
+#[derive(Serialize, PartialEq, Eq, PartialOrd, Ord)]
+enum Status {
+    ActiveUser,
+    Pending,
+}
+
+#[derive(Serialize)]
+enum Filter {
+    ByOwner { owner_id: u32 },
+}
+
+#[derive(Serialize)]
+struct Input {
+    user_id: u32,
+    __typename: &'static str,
+    status: Status,
+    filter: Filter,
+}
+
+fn input() -> Input {
+    Input {
+        user_id: 1,
+        __typename: "Input",
+        status: Status::ActiveUser,
+        filter: Filter::ByOwner { owner_id: 2 },
+    }
+}
+
+#[test]
+fn writes_names_as_is_by_default() {
+    let output = serde_graphql_input::to_string(&input()).unwrap();
+
+    assert_eq!(
+        r#"{user_id:1,__typename:"Input",status:ActiveUser,filter:{ByOwner:{owner_id:2}}}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_rename_to_camel_case() {
+    let output = common::to_string_with(&input(), |ser| {
+        ser.with_rename_policy(RenamePolicy::CamelCase)
+    })
+    .unwrap();
+
+    assert_eq!(
+        r#"{userId:1,__typename:"Input",status:activeUser,filter:{byOwner:{ownerId:2}}}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_rename_to_pascal_case() {
+    let output = common::to_string_with(&input(), |ser| {
+        ser.with_rename_policy(RenamePolicy::PascalCase)
+    })
+    .unwrap();
+
+    assert_eq!(
+        r#"{UserId:1,__Typename:"Input",Status:ActiveUser,Filter:{ByOwner:{OwnerId:2}}}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_rename_to_screaming_snake_case() {
+    let output = common::to_string_with(&[Status::ActiveUser, Status::Pending], |ser| {
+        ser.with_rename_policy(RenamePolicy::ScreamingSnakeCase)
+    })
+    .unwrap();
+
+    assert_eq!("[ACTIVE_USER,PENDING]", output.as_str())
+}
+
+#[test]
+fn can_rename_with_a_function() {
+    fn prefixed(name: &str) -> Cow<'_, str> {
+        Cow::Owned(format!("x_{name}"))
+    }
+
+    let output = common::to_string_with(&BTreeMap::from([("a", Status::Pending)]), |ser| {
+        ser.with_rename_policy(RenamePolicy::Custom(prefixed))
+    })
+    .unwrap();
+
+    assert_eq!("{x_a:x_Pending}", output.as_str())
+}
+
+#[test]
+fn validates_renamed_names() {
+    fn spaced(name: &str) -> Cow<'_, str> {
+        Cow::Owned(format!("{name} "))
+    }
+
+    let err = common::to_string_with(&input(), |ser| {
+        ser.with_rename_policy(RenamePolicy::Custom(spaced))
+    })
+    .unwrap_err();
+
+    assert_eq!("`user_id ` is not a valid GraphQL name", err.to_string())
+}
+
+#[test]
+fn rejects_renamed_enum_values_that_are_literals() {
+    #[derive(Serialize)]
+    enum Flag {
+        True,
+    }
+
+    let err = common::to_string_with(&Flag::True, |ser| {
+        ser.with_rename_policy(RenamePolicy::CamelCase)
+    })
+    .unwrap_err();
+
+    assert_eq!("`true` is not a valid GraphQL enum value", err.to_string())
+}
+
+#[test]
+fn splits_words_at_underscores_and_case_changes() {
+    let cases = [
+        ("user_id", "userId", "UserId", "USER_ID"),
+        ("userId", "userId", "UserId", "USER_ID"),
+        ("UserId", "userId", "UserId", "USER_ID"),
+        ("USER_ID", "userId", "UserId", "USER_ID"),
+        ("HTTPServer", "httpServer", "HttpServer", "HTTP_SERVER"),
+        (
+            "address2_line",
+            "address2Line",
+            "Address2Line",
+            "ADDRESS2_LINE",
+        ),
+        ("v2Api", "v2Api", "V2Api", "V2_API"),
+        ("__typename", "__typename", "__Typename", "__TYPENAME"),
+        ("a__b", "aB", "AB", "A_B"),
+        ("_", "_", "_", "_"),
+    ];
+
+    for (name, camel, pascal, screaming) in cases {
+        assert_eq!(camel, RenamePolicy::CamelCase.apply(name), "{name}");
+        assert_eq!(pascal, RenamePolicy::PascalCase.apply(name), "{name}");
+        assert_eq!(
+            screaming,
+            RenamePolicy::ScreamingSnakeCase.apply(name),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn can_rename_enum_values_independently() {
+    let mut writer = Vec::new();
+    let mut ser = Serializer::new(&mut writer)
+        .with_rename_policy(RenamePolicy::CamelCase)
+        .with_enum_value_policy(RenamePolicy::ScreamingSnakeCase);
+    input().serialize(&mut ser).unwrap();
+
+    assert_eq!(
+        r#"{userId:1,__typename:"Input",status:ACTIVE_USER,filter:{byOwner:{ownerId:2}}}"#,
+        String::from_utf8(writer).unwrap()
+    );
+
+    let mut writer = Vec::new();
+    let mut ser =
+        Serializer::new(&mut writer).with_enum_value_policy(RenamePolicy::ScreamingSnakeCase);
+    (
+        input(),
+        BTreeMap::from([(Status::Pending, Status::ActiveUser)]),
+    )
+        .serialize(&mut ser)
+        .unwrap();
+
+    assert_eq!(
+        r#"[{user_id:1,__typename:"Input",status:ACTIVE_USER,filter:{ByOwner:{owner_id:2}}},{Pending:ACTIVE_USER}]"#,
+        String::from_utf8(writer).unwrap()
+    );
+}
@@ -7,7 +7,7 @@ fn can_serialize_unit_struct() {
 
     let input = Input;
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"null"#, output.as_str())
 }
@@ -19,7 +19,7 @@ fn can_serialize_newtype_struct() {
 
     let input = Input("something".into());
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#""something""#, output.as_str())
 }
@@ -33,7 +33,7 @@ fn can_serialize_newtype_variant() {
 
     let input = Input::Something;
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"Something"#, output.as_str())
 }
@@ -45,7 +45,7 @@ fn can_serialize_tuplestruct() {
 
     let input = Input(123, "something".into());
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
     assert_eq!(r#"[123,"something"]"#, output.as_str())
 }
@@ -58,7 +58,54 @@ fn can_serialize_tuplevariant() {
 
     let input = Input::T(123, "something".into());
 
-    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+    let output = serde_graphql_input::to_string(&input).unwrap();
 
-    assert_eq!(r#"{"T":[123,"something"]}"#, output.as_str())
+    assert_eq!(r#"{T:[123,"something"]}"#, output.as_str())
+}
+
+#[test]
+fn can_serialize_newtype_variant_with_value() {
+    #[derive(Serialize, Clone, Debug)]
+    #[serde(rename_all = "camelCase")]
+    enum Input {
+        ById(String),
+    }
+
+    let input = Input::ById("1".into());
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(r#"{byId:"1"}"#, output.as_str())
+}
+
+#[test]
+fn can_serialize_structvariant() {
+    #[derive(Serialize, Clone, Debug)]
+    enum Input {
+        S { id: u8, name: String },
+    }
+
+    let input = Input::S {
+        id: 1,
+        name: "something".into(),
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(r#"{S:{id:1,name:"something"}}"#, output.as_str())
+}
+
+#[test]
+fn rejects_variant_names_that_are_not_graphql_names() {
+    #[derive(Serialize, Clone, Debug)]
+    enum Input {
+        #[serde(rename = "by-id")]
+        ById(String),
+    }
+
+    let input = Input::ById("1".into());
+
+    let err = serde_graphql_input::to_string(&input).unwrap_err();
+
+    assert_eq!("`by-id` is not a valid GraphQL name", err.to_string())
 }
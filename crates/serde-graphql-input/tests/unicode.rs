@@ -0,0 +1,108 @@
+use serde::Serialize;
+use serde_graphql_input::{Serializer, StringPolicy, UnicodePolicy};
+
+mod common;
+
+const TEXT: &str = "aæ€😀\"\n";
+
+#[test]
+fn writes_unicode_as_is_by_default() {
+    let output = serde_graphql_input::to_string(&TEXT).unwrap();
+
+    assert_eq!(r#""aæ€😀\"\n""#, output.as_str())
+}
+
+#[test]
+fn can_escape_unicode_as_utf16() {
+    let output = common::to_string_with(&TEXT, |ser| {
+        ser.with_unicode_policy(UnicodePolicy::EscapeUtf16)
+    })
+    .unwrap();
+
+    assert_eq!(r#""a\u00e6\u20ac\ud83d\ude00\"\n""#, output.as_str());
+    assert!(output.is_ascii());
+}
+
+#[test]
+fn can_escape_unicode_as_code_points() {
+    let output = common::to_string_with(&TEXT, |ser| {
+        ser.with_unicode_policy(UnicodePolicy::EscapeCodePoint)
+    })
+    .unwrap();
+
+    assert_eq!(r#""a\u{e6}\u{20ac}\u{1f600}\"\n""#, output.as_str());
+    assert!(output.is_ascii());
+}
+
+#[test]
+fn escapes_chars_and_nested_strings() {
+    #[derive(Serialize)]
+    struct Input {
+        initial: char,
+        tags: Vec<&'static str>,
+    }
+
+    let input = Input {
+        initial: 'Ø',
+        tags: vec!["ok", "𝄞"],
+    };
+    let output = common::to_string_with(&input, |ser| {
+        ser.with_unicode_policy(UnicodePolicy::EscapeUtf16)
+    })
+    .unwrap();
+
+    assert_eq!(
+        r#"{initial:"\u00d8",tags:["ok","\ud834\udd1e"]}"#,
+        output.as_str()
+    );
+}
+
+#[test]
+fn does_not_write_block_strings_with_escapes() {
+    let mut writer = Vec::new();
+    let mut ser = Serializer::new(&mut writer)
+        .with_string_policy(StringPolicy::Block { threshold: 0 })
+        .with_unicode_policy(UnicodePolicy::EscapeCodePoint);
+    "æ\nø".serialize(&mut ser).unwrap();
+    "a\nb".serialize(&mut ser).unwrap();
+
+    assert_eq!(
+        "\"\\u{e6}\\n\\u{f8}\"\"\"\"\na\nb\n\"\"\"",
+        String::from_utf8(writer).unwrap()
+    );
+}
+
+#[test]
+fn escaped_unicode_round_trips() {
+    let cases = [
+        "",
+        "plain ascii",
+        TEXT,
+        "\u{7f}\u{80}\u{7ff}\u{800}\u{ffff}\u{10000}\u{10ffff}",
+    ];
+
+    for policy in [UnicodePolicy::EscapeUtf16, UnicodePolicy::EscapeCodePoint] {
+        for value in cases {
+            let output =
+                common::to_string_with(&value, |ser| ser.with_unicode_policy(policy)).unwrap();
+            let roundtrip: String = serde_graphql_input::from_str(&output).unwrap();
+
+            assert_eq!(value, roundtrip, "{output}");
+        }
+    }
+}
+
+#[test]
+fn rejects_invalid_code_point_escapes() {
+    for input in [
+        r#""\u{}""#,
+        r#""\u{d800}""#,
+        r#""\u{110000}""#,
+        r#""\u{g}""#,
+    ] {
+        assert!(
+            serde_graphql_input::from_str::<String>(input).is_err(),
+            "{input}"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use serde::Serialize;
+use serde_graphql_input::{Map, MaybeUndefined, OneOf, RawGraphQL, Value, Variable};
+
+fn object(fields: Vec<(&str, Value)>) -> Value {
+    Value::Object(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), value))
+            .collect(),
+    )
+}
+
+fn sample() -> Value {
+    object(vec![
+        ("id", Value::Int(1)),
+        ("ratio", Value::Float(0.5)),
+        ("name", Value::String("a".into())),
+        ("active", Value::Boolean(true)),
+        ("status", Value::Enum("ACTIVE".into())),
+        (
+            "tags",
+            Value::List(vec![Value::String("x".into()), Value::Null]),
+        ),
+        ("after", Value::Variable("cursor".into())),
+        (
+            "nested",
+            object(vec![("z", Value::Int(2)), ("a", Value::Int(3))]),
+        ),
+    ])
+}
+
+const SAMPLE: &str = r#"{id:1,ratio:0.5,name:"a",active:true,status:ACTIVE,tags:["x",null],after:$cursor,nested:{z:2,a:3}}"#;
+
+#[test]
+fn serializes_every_variant() {
+    let output = serde_graphql_input::to_string(&sample()).unwrap();
+
+    assert_eq!(SAMPLE, output);
+}
+
+#[test]
+fn deserializes_every_variant() {
+    let value: Value = serde_graphql_input::from_str(SAMPLE).unwrap();
+
+    assert_eq!(sample(), value);
+}
+
+#[test]
+fn keeps_object_order() {
+    let value: Value = serde_graphql_input::from_str("{b:1,a:2,c:3}").unwrap();
+
+    let Value::Object(fields) = &value else {
+        panic!("expected an object, found {value:?}");
+    };
+    let keys: Vec<&str> = fields.keys().map(String::as_str).collect();
+    assert_eq!(vec!["b", "a", "c"], keys);
+    assert_eq!("{b:1,a:2,c:3}", value.to_string());
+}
+
+#[test]
+fn deserializes_scalars() {
+    let cases = [
+        ("null", Value::Null),
+        ("true", Value::Boolean(true)),
+        ("false", Value::Boolean(false)),
+        ("-3", Value::Int(-3)),
+        ("1.5e3", Value::Float(1500.0)),
+        (r#""null""#, Value::String("null".into())),
+        ("RED", Value::Enum("RED".into())),
+        ("$id", Value::Variable("id".into())),
+        ("[]", Value::List(vec![])),
+        ("{}", Value::Object(Map::new())),
+    ];
+
+    for (input, expected) in cases {
+        let value: Value = serde_graphql_input::from_str(input).unwrap();
+        assert_eq!(expected, value, "{input}");
+    }
+}
+
+#[test]
+fn displays_through_the_formatter() {
+    let value = object(vec![
+        ("a", Value::List(vec![Value::Int(1), Value::Int(2)])),
+        ("b", Value::Enum("X".into())),
+    ]);
+
+    assert_eq!("{a:[1,2],b:X}", format!("{value}"));
+    assert_eq!(
+        "{\n  a: [\n    1,\n    2\n  ],\n  b: X\n}",
+        format!("{value:#}")
+    );
+}
+
+#[test]
+fn rejects_invalid_names_when_written() {
+    let err = serde_graphql_input::to_string(&Value::Enum("not-a-name".into())).unwrap_err();
+    assert_eq!("`not-a-name` is not a valid GraphQL name", err.to_string());
+
+    let err = serde_graphql_input::to_string(&Value::Variable("1st".into())).unwrap_err();
+    assert_eq!("`1st` is not a valid GraphQL name", err.to_string());
+}
+
+#[test]
+fn rejects_literal_names_as_enum_values() {
+    for name in ["true", "false", "null"] {
+        let err = serde_graphql_input::to_string(&Value::Enum(name.into())).unwrap_err();
+        assert_eq!(
+            format!("`{name}` is not a valid GraphQL enum value"),
+            err.to_string()
+        );
+        assert!(err.is_data());
+    }
+
+    let value = object(vec![("null", Value::Boolean(true))]);
+    assert_eq!(
+        "{null:true}",
+        serde_graphql_input::to_string(&value).unwrap()
+    );
+}
+
+#[test]
+fn displays_values_the_policies_reject() {
+    assert_eq!("3000000000", Value::Int(3_000_000_000).to_string());
+    assert_eq!("null", Value::Float(f64::NAN).to_string());
+    assert_eq!("not-a-name", Value::Enum("not-a-name".into()).to_string());
+
+    let value = object(vec![
+        ("a", Value::List(vec![])),
+        ("b", Value::Object(Map::new())),
+    ]);
+    assert_eq!("{a:[],b:{}}", value.to_string());
+    assert_eq!("{\n  a: [],\n  b: {}\n}", format!("{value:#}"));
+
+    assert!(serde_graphql_input::to_string(&Value::Int(3_000_000_000)).is_err());
+}
+
+#[derive(Serialize)]
+enum Color {
+    Red,
+}
+
+#[derive(Serialize)]
+enum Lookup {
+    ById(u32),
+}
+
+#[derive(Serialize)]
+struct Input {
+    id: u64,
+    color: Color,
+    name: Option<&'static str>,
+    after: Variable<&'static str>,
+    lookup: OneOf<Lookup>,
+    raw: RawGraphQL,
+    skipped: MaybeUndefined<u32>,
+    cleared: MaybeUndefined<u32>,
+    bytes: serde_bytes::ByteBuf,
+}
+
+#[test]
+fn converts_serializable_values() {
+    let input = Input {
+        id: 7,
+        color: Color::Red,
+        name: None,
+        after: Variable("cursor"),
+        lookup: OneOf(Lookup::ById(3)),
+        raw: RawGraphQL::from_string("[A,{b:1}]".into()).unwrap(),
+        skipped: MaybeUndefined::Undefined,
+        cleared: MaybeUndefined::Null,
+        bytes: serde_bytes::ByteBuf::from(b"hi".to_vec()),
+    };
+
+    let value = serde_graphql_input::to_value(&input).unwrap();
+
+    assert_eq!(
+        object(vec![
+            ("id", Value::Int(7)),
+            ("color", Value::Enum("Red".into())),
+            ("name", Value::Null),
+            ("after", Value::Variable("cursor".into())),
+            ("lookup", object(vec![("byId", Value::Int(3))])),
+            (
+                "raw",
+                Value::List(vec![
+                    Value::Enum("A".into()),
+                    object(vec![("b", Value::Int(1))]),
+                ]),
+            ),
+            ("cleared", Value::Null),
+            ("bytes", Value::String("aGk=".into())),
+        ]),
+        value
+    );
+    assert_eq!(
+        serde_graphql_input::to_string(&input).unwrap(),
+        value.to_string()
+    );
+}
+
+#[test]
+fn converts_values_into_themselves() {
+    let value = serde_graphql_input::to_value(&sample()).unwrap();
+
+    assert_eq!(sample(), value);
+}
+
+#[test]
+fn rejects_integers_beyond_i64() {
+    let err = serde_graphql_input::to_value(&u64::MAX).unwrap_err();
+
+    assert_eq!(
+        "`18446744073709551615` is out of range for a GraphQL Int",
+        err.to_string()
+    );
+}
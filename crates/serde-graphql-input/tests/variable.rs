@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use serde_graphql_input::Variable;
+
+#[test]
+fn can_serialize_variables() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        id: Variable<&'static str>,
+        name: String,
+        tags: Vec<Variable>,
+    }
+
+    let input = Input {
+        id: Variable("id"),
+        name: "$notAVariable".into(),
+        tags: vec![Variable("first".into()), Variable("_second".into())],
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+
+    assert_eq!(
+        r#"{id:$id,name:"$notAVariable",tags:[$first,$_second]}"#,
+        output.as_str()
+    )
+}
+
+#[test]
+fn can_pretty_print_variables() {
+    #[derive(Serialize, Clone, Debug)]
+    struct Input {
+        id: Option<Variable<&'static str>>,
+    }
+
+    let input = Input {
+        id: Some(Variable("id")),
+    };
+
+    let output = serde_graphql_input::to_string_pretty(&input).unwrap();
+
+    assert_eq!("{\n  id: $id\n}", output.as_str())
+}
+
+#[test]
+fn rejects_invalid_variable_names() {
+    let err = serde_graphql_input::to_string(&Variable("1id")).unwrap_err();
+    assert_eq!("`1id` is not a valid GraphQL name", err.to_string());
+
+    let err = serde_graphql_input::to_string(&Variable("$id")).unwrap_err();
+    assert_eq!("`$id` is not a valid GraphQL name", err.to_string());
+
+    let err = serde_graphql_input::to_string(&Variable("")).unwrap_err();
+    assert_eq!("`` is not a valid GraphQL name", err.to_string());
+}
+
+#[test]
+fn displays_as_reference() {
+    assert_eq!("$id", Variable("id").to_string())
+}
+
+#[test]
+fn can_roundtrip_variables() {
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Input {
+        id: Variable,
+        other: Option<Variable>,
+    }
+
+    let input = Input {
+        id: Variable("id".into()),
+        other: None,
+    };
+
+    let output = serde_graphql_input::to_string(&input).unwrap();
+    let roundtrip: Input = serde_graphql_input::from_str(&output).unwrap();
+
+    assert_eq!(input, roundtrip);
+
+    let err = serde_graphql_input::from_str::<Input>(r#"{id:"id"}"#).unwrap_err();
+    assert_eq!("expected variable at line 1 column 5", err.to_string());
+}